@@ -1,203 +1,918 @@
-use crate::models::Quiz;
-use crate::quiz_loader::{load_quiz_list, load_quizzes};
+use crate::charts;
+use crate::config::AppConfig;
+use crate::history::{self, quiz_stats, Attempt, QuestionRecord, QuizStats};
+use crate::models::{
+    Answer, Difficulty, Hotspot, QuestionKind, Quiz, QuizMetadata, Region, Scoring, SessionMode, ShuffleMode, UserAnswer,
+};
+use crate::quiz_loader::{load_quiz_file, load_quiz_list, Category, QuizLoadError};
+use crate::scoring::{format_points, question_points, question_score, session_score};
+use crate::session::{self, question_layout, SavedQuestion, SavedSession};
+use crate::shuffle::{random_seed, seed_from_str, shuffle_session, shuffled_indices};
+use crate::study::{self, card_key, quality, Card, Confidence, Schedule, NEW_CARDS_PER_SESSION};
+use crate::text_match::search_key;
+use crate::validation::{has_errors, validate_quiz_file, Issue, Severity};
+use crate::watcher::{absolute_path, LibraryWatcher};
 use eframe::egui;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Image de question affichée en plein écran, avec zoom et déplacement.
+struct Lightbox {
+    image: String,
+    /// Zone de l'image visible, modifiée par egui::Scene au fil des zooms et déplacements
+    scene_rect: egui::Rect,
+}
+
+/// Position dans la revue des réponses, affichée après les résultats.
+struct Review {
+    /// Rang dans la liste des questions revues (toutes, ou seulement les erreurs)
+    position: usize,
+    only_mistakes: bool,
+}
+
+/// Session « Refaire les erreurs » : les questions ratées sont reposées jusqu'à ce que tout soit juste.
+struct Mastery {
+    /// Questions du quiz complet ; `MyApp::questions` ne contient que celles du tour en cours
+    all_questions: Vec<Quiz>,
+    /// Indice dans `all_questions` de chaque question du tour en cours
+    origin: Vec<usize>,
+    /// Nombre de passages de chaque question du quiz complet
+    passes: Vec<u32>,
+    round: u32,
+}
+
+/// Session de révision espacée : questions à revoir aujourd'hui, tirées de tous les quiz.
+struct Study {
+    schedule: Schedule,
+    /// Clé de calendrier de chaque question de la session
+    keys: Vec<String>,
+    /// État des cartes avant la session, pour pouvoir revenir sur la confiance déclarée
+    before: Vec<Option<Card>>,
+    confidence: Vec<Option<Confidence>>,
+    graded: Vec<bool>,
+}
+
+impl Study {
+    /// Replanifie une question d'après sa note et la confiance déclarée.
+    fn grade(&mut self, index: usize, score: f32) {
+        let today = chrono::Local::now().date_naive();
+        let card = study::review(self.before[index].as_ref(), quality(score, self.confidence[index]), today);
+        self.schedule.insert(self.keys[index].clone(), card);
+        self.graded[index] = true;
+        if let Err(err) = study::save(&self.schedule) {
+            eprintln!("Impossible d'enregistrer le calendrier de révision : {}", err);
+        }
+    }
+}
+
+/// Proposition de reprendre une session inachevée, affichée à l'ouverture d'un quiz.
+struct ResumePrompt {
+    saved: SavedSession,
+    /// Question enregistrée correspondant à chaque question actuelle, ou la raison pour laquelle
+    /// la reprise est impossible (quiz modifié depuis)
+    mapping: Result<Vec<Option<usize>>, String>,
+}
+
+/// Dernier état enregistré de la session, pour n'écrire sur disque que lorsqu'il change.
+struct Autosave {
+    at: Instant,
+    question: usize,
+    answers: Vec<Option<UserAnswer>>,
+    checked: Vec<bool>,
+}
+
+/// Délai maximal entre deux enregistrements, pour que le temps écoulé reste à jour.
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// État d'un fichier de quiz tel qu'affiché dans l'écran de sélection.
+enum QuizStatus {
+    Valid,
+    Invalid(Vec<Issue>),
+    Unreadable(String),
+}
+
+/// Quiz de la bibliothèque tel qu'affiché dans l'écran de sélection. Tout ce qu'il faut pour
+/// filtrer et trier la liste est calculé une fois, pas à chaque image.
+struct LibraryEntry {
+    status: QuizStatus,
+    metadata: QuizMetadata,
+    /// Titre, ou fichier sans extension
+    name: String,
+    /// `name` mis en forme par `search_key`, pour le tri par nom
+    sort_name: String,
+    /// Texte affiché au survol (voir `QuizMetadata::summary`)
+    summary: String,
+    /// Nom, chemin relatif, tags et énoncés des questions, mis en forme par `search_key`
+    search_text: String,
+    /// Questions dans l'ordre du fichier, reprises par les révisions du jour
+    questions: Vec<Quiz>,
+    /// Statistiques de l'historique et session inachevée, à jour via `MyApp::refresh_entry_stats`
+    stats: Option<QuizStats>,
+    has_session: bool,
+}
+
+impl LibraryEntry {
+    fn new(relative: &Path, status: QuizStatus, metadata: QuizMetadata, questions: Vec<Quiz>) -> Self {
+        let name = display_name(relative, &metadata);
+        // Le chemin relatif permet de chercher aussi par dossier (catégorie)
+        let mut text = format!("{}\n{}", name, relative_key(relative));
+        for tag in &metadata.tags {
+            text.push('\n');
+            text.push_str(tag);
+        }
+        for quiz in &questions {
+            text.push('\n');
+            text.push_str(&quiz.question);
+        }
+        Self {
+            status,
+            sort_name: search_key(&name),
+            name,
+            summary: metadata.summary(),
+            metadata,
+            search_text: search_key(&text),
+            questions,
+            stats: None,
+            has_session: false,
+        }
+    }
+}
+
+/// Recherche, filtres et tri de l'écran de sélection.
+#[derive(Default)]
+struct LibraryFilter {
+    search: String,
+    tag: Option<String>,
+    difficulty: Option<Difficulty>,
+    language: Option<String>,
+    never_attempted: bool,
+    failed_last: bool,
+    sort: LibrarySort,
+}
+
+impl LibraryFilter {
+    fn is_active(&self) -> bool {
+        !self.search.trim().is_empty()
+            || self.tag.is_some()
+            || self.difficulty.is_some()
+            || self.language.is_some()
+            || self.never_attempted
+            || self.failed_last
+    }
+
+    /// `search` : la recherche mise en forme par `search_key`, calculée une fois pour toute la liste.
+    fn matches(&self, entry: &LibraryEntry, search: &str) -> bool {
+        let metadata = &entry.metadata;
+        let stats = entry.stats.as_ref();
+        (search.is_empty() || entry.search_text.contains(search))
+            && self.tag.as_ref().is_none_or(|tag| metadata.tags.contains(tag))
+            && self.difficulty.is_none_or(|difficulty| metadata.difficulty == Some(difficulty))
+            && self.language.as_ref().is_none_or(|language| metadata.language.as_ref() == Some(language))
+            && (!self.never_attempted || stats.is_none())
+            && (!self.failed_last || stats.is_some_and(|stats| stats.last_failed))
+    }
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+enum LibrarySort {
+    #[default]
+    Name,
+    /// Du plus récemment joué au jamais joué
+    LastPlayed,
+    /// Du meilleur score au jamais joué
+    BestScore,
+}
+
+impl LibrarySort {
+    const ALL: [LibrarySort; 3] = [LibrarySort::Name, LibrarySort::LastPlayed, LibrarySort::BestScore];
+
+    fn label(self) -> &'static str {
+        match self {
+            LibrarySort::Name => "Nom",
+            LibrarySort::LastPlayed => "Dernière session",
+            LibrarySort::BestScore => "Meilleur score",
+        }
+    }
+}
+
+/// Clé identifiant un quiz dans l'historique, les sessions et les révisions : `metadata.id`, ou à défaut
+/// son chemin relatif à la bibliothèque, qui ne change pas quand le dossier des quiz est déplacé.
+fn quiz_key(relative: &Path, metadata: &QuizMetadata) -> String {
+    metadata.id.clone().unwrap_or_else(|| relative_key(relative))
+}
+
+/// Chemin relatif avec des « / », identique d'un système à l'autre.
+fn relative_key(relative: &Path) -> String {
+    relative
+        .iter()
+        .map(|part| part.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
 
 pub struct MyApp {
     questions: Vec<Quiz>,
     current_question_index: usize,
-    selected_answer: Option<usize>,
-    user_answers: Vec<Option<String>>,
+    user_answers: Vec<Option<UserAnswer>>,
+    scoring: Scoring,
+    immediate_feedback: bool,
+    session_mode: SessionMode,
+    session_mode_override: Option<SessionMode>,
+    shuffle: ShuffleMode,
+    shuffle_override: Option<ShuffleMode>,
+    shuffle_seed: u64, // graine de la session en cours, pour la rejouer à l'identique
+    shuffle_seed_override: Option<u64>,
+    time_limit: Option<Duration>, // durée accordée pour tout le quiz
+    time_spent: Vec<Duration>,    // temps passé sur chaque question
+    elapsed: Duration,            // temps écoulé depuis le début du quiz, même sur une question expirée
+    last_tick: Option<Instant>,   // dernier décompte, `None` quand le chronomètre est à l'arrêt
+    checked: Vec<bool>, // questions vérifiées et verrouillées en mode entraînement
     show_results: bool,
+    review: Option<Review>,
+    mastery: Option<Mastery>,
     quiz_path: String,
-    quiz_files: Vec<String>,
+    quiz_files: Vec<PathBuf>, // chemins relatifs à `quizzes_dir`
     show_quiz_selection: bool,
-    quizzes_dir: String,
+    quizzes_dir: PathBuf,
     image_cache: std::collections::HashMap<String, Vec<u8>>, // cache des bytes des images
+    lightbox: Option<Lightbox>,
+    load_error: Option<QuizLoadError>,
+    quiz_issues: Vec<Issue>,
+    library: HashMap<PathBuf, LibraryEntry>, // état et en-tête des quiz de la liste
+    library_filter: LibraryFilter,
+    metadata: QuizMetadata,                        // en-tête du quiz chargé
+    content_hash: u64, // empreinte du quiz chargé, enregistrée avec la session
+    resume_prompt: Option<ResumePrompt>,
+    autosave: Option<Autosave>,
+    history: Vec<Attempt>,
+    show_history: bool,
+    study: Option<Study>,
+    study_due: usize, // questions à réviser aujourd'hui, tous quiz confondus
+    watcher: Option<LibraryWatcher>,
+    reload_snapshot: Option<SavedSession>, // réponses gardées tant que le quiz modifié ne se charge pas
 }
 
 impl MyApp {
-    pub fn new(quiz_path: &str) -> Self {
+    pub fn new(config: &AppConfig, ctx: &egui::Context) -> Self {
+        let quiz_path = config.quiz_path.as_str();
         // Si quiz_path est un dossier, l'utiliser directement, sinon extraire le parent
-        let quizzes_dir = if std::path::Path::new(quiz_path).is_dir() {
-            quiz_path.to_string()
+        let quizzes_dir = if Path::new(quiz_path).is_dir() {
+            PathBuf::from(quiz_path)
         } else {
-            std::path::Path::new(quiz_path)
+            Path::new(quiz_path)
                 .parent()
-                .and_then(|p| p.to_str())
-                .unwrap_or("Quizzes")
-                .to_string()
+                .map_or_else(|| PathBuf::from("Quizzes"), Path::to_path_buf)
         };
         
         let quiz_files = load_quiz_list(&quizzes_dir);
         let show_quiz_selection = quiz_files.len() > 1;
         
-        // Si un seul quiz, le charger automatiquement
-        let questions = if quiz_files.len() == 1 {
-            let first_quiz = format!("{}/{}", quizzes_dir, quiz_files[0]);
-            load_quizzes(&first_quiz)
-        } else if show_quiz_selection {
-            Vec::new()
-        } else {
-            load_quizzes(quiz_path)
-        };
-        
-        let user_answers = vec![None; questions.len()];
-        Self {
-            questions,
+        let mut app = Self {
+            questions: Vec::new(),
             current_question_index: 0,
-            selected_answer: None,
-            user_answers,
+            user_answers: Vec::new(),
+            scoring: Scoring::default(),
+            immediate_feedback: false,
+            session_mode: SessionMode::default(),
+            session_mode_override: config.session_mode,
+            shuffle: ShuffleMode::default(),
+            shuffle_override: config.shuffle,
+            shuffle_seed: 0,
+            shuffle_seed_override: config.shuffle_seed,
+            time_limit: None,
+            time_spent: Vec::new(),
+            elapsed: Duration::ZERO,
+            last_tick: None,
+            checked: Vec::new(),
             show_results: false,
+            review: None,
+            mastery: None,
             quiz_path: quiz_path.to_string(),
             quiz_files,
             show_quiz_selection,
             quizzes_dir,
             image_cache: std::collections::HashMap::new(),
+            lightbox: None,
+            load_error: None,
+            quiz_issues: Vec::new(),
+            library: HashMap::new(),
+            library_filter: LibraryFilter::default(),
+            metadata: QuizMetadata::default(),
+            content_hash: 0,
+            resume_prompt: None,
+            autosave: None,
+            history: history::load(),
+            show_history: false,
+            study: None,
+            study_due: 0,
+            watcher: None,
+            reload_snapshot: None,
+        };
+        match LibraryWatcher::new(&app.quizzes_dir, ctx.clone()) {
+            Ok(watcher) => app.watcher = Some(watcher),
+            Err(err) => eprintln!("Impossible de surveiller {} : {}", app.quizzes_dir.display(), err),
+        }
+        app.refresh_quiz_statuses();
+        
+        // Si un seul quiz, le charger automatiquement
+        if app.quiz_files.len() == 1 {
+            let first_quiz = app.library_path(&app.quiz_files[0]);
+            app.open_quiz(first_quiz);
+        } else if !show_quiz_selection {
+            app.open_quiz(quiz_path.to_string());
         }
+        app
     }
     
-    fn load_selected_quiz(&mut self, filename: &str) {
-        let path = format!("{}/{}", self.quizzes_dir, filename);
-        self.questions = load_quizzes(&path);
-        self.user_answers = vec![None; self.questions.len()];
-        self.current_question_index = 0;
-        self.selected_answer = None;
-        self.show_results = false;
+    fn refresh_quiz_statuses(&mut self) {
+        self.library = self
+            .quiz_files
+            .iter()
+            .map(|relative| (relative.clone(), self.check_quiz(relative)))
+            .collect();
+        self.study_due = self.due_cards(&study::load()).len();
+    }
+
+    /// Questions de tous les quiz valides à réviser aujourd'hui, puis quelques questions jamais révisées.
+    fn due_cards(&self, schedule: &Schedule) -> Vec<(String, Quiz)> {
+        let today = chrono::Local::now().date_naive();
+        let mut due = Vec::new();
+        let mut new = Vec::new();
+        for relative in &self.quiz_files {
+            let Some(entry) = self.library.get(relative) else { continue };
+            if !matches!(entry.status, QuizStatus::Valid) {
+                continue;
+            }
+            let quiz_key = quiz_key(relative, &entry.metadata);
+            for quiz in &entry.questions {
+                let key = card_key(&quiz_key, quiz);
+                match schedule.get(&key) {
+                    Some(card) if card.due <= today => due.push((key, quiz.clone())),
+                    Some(_) => {}
+                    None => new.push((key, quiz.clone())),
+                }
+            }
+        }
+        new.truncate(NEW_CARDS_PER_SESSION);
+        due.extend(new);
+        due
+    }
+
+    /// Démarre une session de révision, en mode entraînement et avec le barème par défaut.
+    fn start_study(&mut self) {
+        let schedule = study::load();
+        let (keys, questions): (Vec<String>, Vec<Quiz>) = self.due_cards(&schedule).into_iter().unzip();
+        let count = questions.len();
+        self.questions = questions;
+        self.scoring = Scoring::default();
+        self.immediate_feedback = false;
+        self.session_mode = SessionMode::Practice;
+        self.shuffle = ShuffleMode::None;
+        self.shuffle_seed = random_seed();
+        self.time_limit = None;
+        self.quiz_path = String::new();
+        self.metadata = QuizMetadata::default();
+        self.quiz_issues.clear();
+        self.load_error = None;
+        self.mastery = None;
+        self.start_session();
+        self.show_quiz_selection = false;
+        self.study = Some(Study {
+            before: keys.iter().map(|key| schedule.get(key).cloned()).collect(),
+            schedule,
+            keys,
+            confidence: vec![None; count],
+            graded: vec![false; count],
+        });
+    }
+
+    /// Replanifie les questions qui viennent d'être vérifiées pendant une session de révision.
+    fn grade_study(&mut self) {
+        let Some(study) = &mut self.study else { return };
+        for (i, quiz) in self.questions.iter().enumerate() {
+            if self.checked[i] && !study.graded[i] {
+                study.grade(i, question_score(quiz, self.user_answers[i].as_ref(), &self.scoring));
+            }
+        }
+    }
+
+    /// Chemin d'un quiz de la bibliothèque à partir de son chemin relatif.
+    fn library_path(&self, relative: &Path) -> String {
+        self.quizzes_dir.join(relative).to_string_lossy().into_owned()
+    }
+
+    /// Relit un quiz de la bibliothèque pour l'écran de sélection.
+    fn check_quiz(&self, relative: &Path) -> LibraryEntry {
+        let mut entry = match load_quiz_file(&self.library_path(relative)) {
+            Ok(quiz_file) => {
+                let issues = validate_quiz_file(&quiz_file);
+                let status = if has_errors(&issues) { QuizStatus::Invalid(issues) } else { QuizStatus::Valid };
+                LibraryEntry::new(relative, status, quiz_file.metadata, quiz_file.questions)
+            }
+            Err(err) => {
+                let status = QuizStatus::Unreadable(err.to_string());
+                LibraryEntry::new(relative, status, QuizMetadata::default(), Vec::new())
+            }
+        };
+        let key = quiz_key(relative, &entry.metadata);
+        entry.stats = quiz_stats(&self.history, &key);
+        entry.has_session = session::exists(&key);
+        entry
+    }
+
+    /// Met à jour les statistiques et la session inachevée affichées pour un quiz de la bibliothèque.
+    fn refresh_entry_stats(&mut self, relative: &Path) {
+        let Some(entry) = self.library.get(relative) else { return };
+        let key = quiz_key(relative, &entry.metadata);
+        let stats = quiz_stats(&self.history, &key);
+        let has_session = session::exists(&key);
+        if let Some(entry) = self.library.get_mut(relative) {
+            entry.stats = stats;
+            entry.has_session = has_session;
+        }
+    }
+
+    fn load_selected_quiz(&mut self, relative: &Path) {
+        self.open_quiz(self.library_path(relative));
+    }
+
+    /// Charge un quiz et propose de reprendre la session inachevée enregistrée pour lui.
+    fn open_quiz(&mut self, path: String) {
+        self.load_quiz_path(path);
+        if self.has_blocking_problem() {
+            return;
+        }
+        let Some(saved) = session::load(&self.quiz_key()) else { return };
+        // Même mélange que la session enregistrée, pour retrouver l'ordre de ses questions
+        self.load_quiz_path_shuffled(self.quiz_path.clone(), Some((saved.shuffle, saved.shuffle_seed)));
+        let mapping = saved.match_questions(self.content_hash, &self.questions);
+        self.resume_prompt = Some(ResumePrompt { saved, mapping });
+    }
+
+    fn quiz_key(&self) -> String {
+        quiz_key(&self.relative_quiz_path(), &self.metadata)
+    }
+
+    /// Chemin du quiz chargé relatif à la bibliothèque (inchangé s'il est en dehors).
+    fn relative_quiz_path(&self) -> PathBuf {
+        let path = Path::new(&self.quiz_path);
+        path.strip_prefix(&self.quizzes_dir).unwrap_or(path).to_path_buf()
+    }
+
+    fn load_quiz_path(&mut self, path: String) {
+        self.load_quiz_path_shuffled(path, None);
+    }
+
+    /// Charge un quiz ; `shuffle` impose le mélange et sa graine (reprise d'une session enregistrée).
+    fn load_quiz_path_shuffled(&mut self, path: String, shuffle: Option<(ShuffleMode, u64)>) {
+        match load_quiz_file(&path) {
+            Ok(quiz_file) => {
+                self.quiz_issues = validate_quiz_file(&quiz_file);
+                self.study = None;
+                self.content_hash = session::content_hash(&path);
+                self.metadata = quiz_file.metadata;
+                (self.shuffle, self.shuffle_seed) = shuffle.unwrap_or_else(|| {
+                    (
+                        self.shuffle_override.or(quiz_file.shuffle).unwrap_or_default(),
+                        self.shuffle_seed_override.or(quiz_file.shuffle_seed).unwrap_or_else(random_seed),
+                    )
+                });
+                self.questions = shuffle_session(quiz_file.questions, self.shuffle, self.shuffle_seed);
+                self.scoring = quiz_file.scoring;
+                self.immediate_feedback = quiz_file.immediate_feedback;
+                self.session_mode = self.session_mode_override.or(quiz_file.mode).unwrap_or_default();
+                self.time_limit = quiz_file.time_limit.map(Duration::from_secs);
+                self.load_error = None;
+            }
+            Err(err) => {
+                self.questions = Vec::new();
+                self.metadata = QuizMetadata::default();
+                self.quiz_issues = Vec::new();
+                self.load_error = Some(err);
+            }
+        }
+        // Mettre à jour le statut affiché dans l'écran de sélection, avec les questions dans l'ordre du fichier
+        if let Ok(relative) = Path::new(&path).strip_prefix(&self.quizzes_dir)
+            && self.library.contains_key(relative)
+        {
+            let entry = self.check_quiz(relative);
+            self.library.insert(relative.to_path_buf(), entry);
+        }
+        self.mastery = None;
+        self.start_session();
         self.show_quiz_selection = false;
         self.quiz_path = path;
         self.image_cache.clear(); // réinitialiser le cache pour le nouveau quiz
     }
 
+    /// Repart de la première question avec des réponses vides.
+    fn start_session(&mut self) {
+        self.user_answers = vec![None; self.questions.len()];
+        self.checked = vec![false; self.questions.len()];
+        self.time_spent = vec![Duration::ZERO; self.questions.len()];
+        self.elapsed = Duration::ZERO;
+        self.last_tick = None;
+        self.current_question_index = 0;
+        self.show_results = false;
+        self.review = None;
+        self.lightbox = None;
+        self.resume_prompt = None;
+        self.autosave = None;
+        self.reload_snapshot = None;
+    }
+
+    /// Reprend une session enregistrée ; le quiz a déjà été rechargé avec son mélange
+    /// (voir `open_quiz` et `reload_current_quiz`).
+    fn resume_session(&mut self, mut saved: SavedSession, mapping: &[Option<usize>]) {
+        for (i, origin) in mapping.iter().enumerate() {
+            let Some(saved_question) = origin.and_then(|j| saved.questions.get_mut(j)) else { continue };
+            self.user_answers[i] = saved_question.answer.take();
+            self.checked[i] = saved_question.checked;
+            self.time_spent[i] = Duration::from_secs_f64(saved_question.seconds.max(0.0));
+        }
+        let spent: Duration = self.time_spent.iter().sum();
+        self.elapsed = Duration::from_secs_f64(saved.elapsed.max(0.0)).max(spent);
+        self.current_question_index = mapping
+            .iter()
+            .position(|origin| *origin == Some(saved.current_question))
+            .unwrap_or(0);
+    }
+
+    /// Enregistre la session en cours quand les réponses ou la question affichée changent,
+    /// et régulièrement pour le temps écoulé.
+    fn autosave(&mut self) {
+        // Les tours de « Refaire les erreurs » et les révisions ne correspondent pas à un fichier : pas de reprise
+        if self.mastery.is_some() || self.study.is_some() || self.resume_prompt.is_some() {
+            return;
+        }
+        // Session terminée dans cette image (« Terminer » ou temps écoulé) : déjà supprimée du disque
+        if self.show_results {
+            return;
+        }
+        if !self.user_answers.iter().flatten().any(|answer| !answer.is_empty()) {
+            return;
+        }
+        let index = self.current_question_index;
+        let unchanged = self.autosave.as_ref().is_some_and(|last| {
+            last.question == index
+                && last.answers == self.user_answers
+                && last.checked == self.checked
+                && last.at.elapsed() < AUTOSAVE_INTERVAL
+        });
+        if unchanged {
+            return;
+        }
+
+        if let Err(err) = session::save(&self.saved_session()) {
+            eprintln!("Impossible d'enregistrer la session : {}", err);
+        }
+        if !self.library.get(&self.relative_quiz_path()).is_some_and(|entry| entry.has_session) {
+            self.refresh_entry_stats(&self.relative_quiz_path());
+        }
+        self.autosave = Some(Autosave {
+            at: Instant::now(),
+            question: index,
+            answers: self.user_answers.clone(),
+            checked: self.checked.clone(),
+        });
+    }
+
+    /// État de la session en cours, tel qu'enregistré pour la reprendre.
+    fn saved_session(&self) -> SavedSession {
+        SavedSession {
+            quiz_key: self.quiz_key(),
+            content_hash: self.content_hash,
+            shuffle: self.shuffle,
+            shuffle_seed: self.shuffle_seed,
+            current_question: self.current_question_index,
+            elapsed: self.elapsed.as_secs_f64(),
+            questions: self
+                .questions
+                .iter()
+                .enumerate()
+                .map(|(i, quiz)| SavedQuestion {
+                    id: quiz.id.clone(),
+                    layout: question_layout(quiz),
+                    answer: self.user_answers[i].clone(),
+                    checked: self.checked[i],
+                    seconds: self.time_spent[i].as_secs_f64(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Prend en compte les fichiers modifiés sur le disque depuis l'image précédente.
+    fn apply_file_changes(&mut self, ctx: &egui::Context) {
+        let Some(watcher) = &self.watcher else { return };
+        let changed = watcher.changed_paths();
+        if changed.is_empty() {
+            return;
+        }
+
+        // Images modifiées : oublier leurs octets et la texture déjà chargée par egui
+        self.image_cache.retain(|image_path, _| {
+            let stale = changed.contains(&absolute_path(image_path));
+            if stale {
+                ctx.forget_image(&format!("bytes://{}", image_path));
+            }
+            !stale
+        });
+
+        self.refresh_library(&changed);
+
+        // Les révisions et les tours de « Refaire les erreurs » ne portent pas sur le fichier tel quel
+        let current_changed = !self.quiz_path.is_empty() && changed.contains(&absolute_path(&self.quiz_path));
+        if current_changed && !self.show_quiz_selection && self.study.is_none() && self.mastery.is_none() {
+            self.reload_current_quiz();
+        }
+    }
+
+    /// Met à jour la liste des quiz ; seuls les fichiers nouveaux ou modifiés sont relus.
+    fn refresh_library(&mut self, changed: &[PathBuf]) {
+        self.quiz_files = load_quiz_list(&self.quizzes_dir);
+        let count = self.library.len();
+        let quiz_files = &self.quiz_files;
+        self.library.retain(|relative, _| quiz_files.contains(relative));
+        let mut modified = self.library.len() != count;
+        let outdated: Vec<PathBuf> = self
+            .quiz_files
+            .iter()
+            .filter(|relative| {
+                !self.library.contains_key(*relative) || changed.contains(&absolute_path(self.library_path(relative)))
+            })
+            .cloned()
+            .collect();
+        for relative in outdated {
+            let entry = self.check_quiz(&relative);
+            self.library.insert(relative, entry);
+            modified = true;
+        }
+        if modified {
+            self.study_due = self.due_cards(&study::load()).len();
+        }
+    }
+
+    /// Recharge le quiz en cours après modification de son fichier, avec le même mélange ;
+    /// les questions retrouvées gardent leur réponse (voir `SavedSession::matching_questions`).
+    fn reload_current_quiz(&mut self) {
+        if self.resume_prompt.is_some() {
+            self.open_quiz(self.quiz_path.clone());
+            return;
+        }
+        // Un fichier en cours d'écriture peut être momentanément invalide : les réponses
+        // d'avant l'erreur restent en attente du prochain rechargement réussi
+        let snapshot = self.reload_snapshot.take().unwrap_or_else(|| self.saved_session());
+        let show_results = self.show_results;
+        self.load_quiz_path_shuffled(self.quiz_path.clone(), Some((self.shuffle, self.shuffle_seed)));
+        if self.has_blocking_problem() {
+            self.reload_snapshot = Some(snapshot);
+            return;
+        }
+        let mapping = snapshot.matching_questions(&self.questions);
+        self.resume_session(snapshot, &mapping);
+        self.show_results = show_results;
+    }
+
+    /// Affiche les résultats ; la session terminée n'est plus proposée à la reprise.
+    fn finish_session(&mut self) {
+        self.show_results = true;
+        if self.study.is_some() {
+            self.study_due = self.due_cards(&study::load()).len();
+        } else if self.mastery.is_none() {
+            session::remove(&self.quiz_key());
+            self.record_attempt();
+            self.refresh_entry_stats(&self.relative_quiz_path());
+        }
+    }
+
+    /// Ajoute la session terminée à l'historique local.
+    fn record_attempt(&mut self) {
+        let score = session_score(&self.questions, &self.user_answers, &self.scoring);
+        let questions = self
+            .questions
+            .iter()
+            .enumerate()
+            .map(|(i, quiz)| {
+                let answer = self.user_answers[i].as_ref();
+                QuestionRecord {
+                    id: quiz.id.clone(),
+                    question: quiz.question.clone(),
+                    answer: answer.filter(|a| !a.is_empty()).map(|a| quiz.answer_label(a)),
+                    score: question_score(quiz, answer, &self.scoring),
+                    seconds: self.time_spent[i].as_secs_f64(),
+                }
+            })
+            .collect();
+        let attempt = Attempt {
+            date: chrono::Local::now(),
+            quiz_path: relative_key(&self.relative_quiz_path()),
+            quiz_id: self.metadata.id.clone(),
+            quiz_title: self.metadata.title.clone(),
+            points: score.points,
+            max_points: score.max_points,
+            passed: score.passed,
+            shuffle: self.shuffle,
+            shuffle_seed: (self.shuffle != ShuffleMode::None).then_some(self.shuffle_seed),
+            questions,
+        };
+        if let Err(err) = history::append(&attempt) {
+            eprintln!("Impossible d'enregistrer l'historique : {}", err);
+        }
+        self.history.push(attempt);
+    }
+
+    /// Nouveau tour limité aux questions fausses ou sans réponse du tour qui vient de se terminer.
+    fn retry_mistakes(&mut self) {
+        let scores = self.question_scores();
+        let count = self.questions.len();
+        let mastery = self.mastery.get_or_insert_with(|| Mastery {
+            all_questions: self.questions.clone(),
+            origin: (0..count).collect(),
+            passes: vec![1; count],
+            round: 1,
+        });
+        let origin: Vec<usize> = scores
+            .iter()
+            .zip(&mastery.origin)
+            .filter(|(score, _)| **score < 1.0)
+            .map(|(_, &original)| original)
+            .collect();
+        for &original in &origin {
+            mastery.passes[original] += 1;
+        }
+        mastery.round += 1;
+        self.questions = origin.iter().map(|&original| mastery.all_questions[original].clone()).collect();
+        mastery.origin = origin;
+        self.start_session();
+    }
+
+    /// Temps restant pour répondre à la question, `None` si elle n'est pas chronométrée.
+    fn question_remaining(&self, index: usize) -> Option<Duration> {
+        let limit = Duration::from_secs(self.questions.get(index)?.time_limit?);
+        Some(limit.saturating_sub(self.time_spent[index]))
+    }
+
+    /// Temps restant pour terminer le quiz, `None` sans durée imposée.
+    fn quiz_remaining(&self) -> Option<Duration> {
+        Some(self.time_limit?.saturating_sub(self.elapsed))
+    }
+
+    fn is_timed_out(&self, index: usize) -> bool {
+        self.question_remaining(index) == Some(Duration::ZERO)
+    }
+
+    /// Ajoute le temps écoulé à la question affichée, puis passe à la suivante ou termine le quiz
+    /// si une limite de temps vient d'expirer.
+    fn tick_timers(&mut self, ctx: &egui::Context) {
+        // Rien ne s'écoule tant que la reprise n'est pas décidée : la session enregistrée ne doit pas expirer
+        if self.resume_prompt.is_some() {
+            self.last_tick = None;
+            return;
+        }
+        let now = Instant::now();
+        let elapsed = self.last_tick.replace(now).map_or(Duration::ZERO, |last| now - last);
+        let index = self.current_question_index;
+        if index >= self.questions.len() {
+            return;
+        }
+
+        self.elapsed += elapsed;
+        let was_timed_out = self.is_timed_out(index);
+        if !was_timed_out {
+            self.time_spent[index] += elapsed;
+        }
+        if self.quiz_remaining() == Some(Duration::ZERO) {
+            self.finish_session();
+            return;
+        }
+        if !was_timed_out && self.is_timed_out(index) {
+            if let Some(limit) = self.questions[index].time_limit {
+                self.time_spent[index] = Duration::from_secs(limit);
+            }
+            if index + 1 < self.questions.len() {
+                self.current_question_index += 1;
+            } else {
+                self.finish_session();
+                return;
+            }
+        }
+        // Garder le décompte à jour à l'écran
+        if self.time_limit.is_some() || self.question_remaining(self.current_question_index).is_some() {
+            ctx.request_repaint_after(Duration::from_millis(250));
+        }
+    }
+
+    /// Numéro de la question dans le quiz complet (les tours de « Refaire les erreurs » n'en reprennent qu'une partie).
+    fn question_number(&self, index: usize) -> usize {
+        self.mastery.as_ref().map_or(index, |mastery| mastery.origin[index]) + 1
+    }
+
+    fn has_blocking_problem(&self) -> bool {
+        self.load_error.is_some() || has_errors(&self.quiz_issues)
+    }
+
+    fn show_load_error_screen(&mut self, ui: &mut egui::Ui) {
+        let path = self
+            .load_error
+            .as_ref()
+            .map_or(self.quiz_path.as_str(), |err| err.path());
+        let file_name = std::path::Path::new(path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(path)
+            .to_string();
+
+        ui.heading("Impossible de charger le quiz");
+        ui.add_space(20.0);
+
+        if let Some(err) = &self.load_error {
+            ui.label(egui::RichText::new(err.kind_label()).strong().color(egui::Color32::RED));
+            ui.label(format!("Fichier : {}", file_name));
+            if let Some(loc) = err.location() {
+                ui.label(format!("Emplacement : {}", loc));
+            }
+            ui.add_space(10.0);
+
+            let message = err.detail();
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                ui.label(egui::RichText::new(message).monospace());
+            });
+        } else {
+            ui.label(egui::RichText::new("Quiz incohérent").strong().color(egui::Color32::RED));
+            ui.label(format!("Fichier : {}", file_name));
+            ui.add_space(10.0);
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for issue in &self.quiz_issues {
+                    let color = match issue.severity {
+                        Severity::Error => egui::Color32::RED,
+                        Severity::Warning => egui::Color32::YELLOW,
+                    };
+                    ui.colored_label(color, issue.to_string());
+                }
+            });
+        }
+
+        egui::TopBottomPanel::bottom("load_error_footer").show_inside(ui, |ui| {
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                if self.quiz_files.len() > 1 && ui.button("◀ Choisir un autre quiz").clicked() {
+                    self.load_error = None;
+                    self.quiz_issues.clear();
+                    self.show_quiz_selection = true;
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("⟳ Recharger").clicked() {
+                        self.load_quiz_path(self.quiz_path.clone());
+                    }
+                });
+            });
+            ui.add_space(5.0);
+        });
+    }
+
     fn show_quiz_screen(&mut self, ui: &mut egui::Ui) {
+        self.tick_timers(ui.ctx());
+        if self.show_results {
+            return;
+        }
+        let timed_out = self.is_timed_out(self.current_question_index);
+        
         // Header avec titre et numéro de question
         egui::TopBottomPanel::top("quiz_header").show_inside(ui, |ui| {
             ui.add_space(10.0);
-            ui.heading("Quiz");
-            ui.label(format!("Question {}/{}", self.current_question_index + 1, self.questions.len()));
+            ui.heading(self.metadata.title.as_deref().unwrap_or("Quiz"));
+            ui.horizontal(|ui| {
+                ui.label(format!("Question {}/{}", self.current_question_index + 1, self.questions.len()));
+                if self.session_mode == SessionMode::Practice {
+                    ui.label(egui::RichText::new("· Mode entraînement").weak());
+                }
+                if let Some(mastery) = &self.mastery {
+                    ui.label(egui::RichText::new(format!("· Refaire les erreurs, tour {}", mastery.round)).weak());
+                }
+                if self.study.is_some() {
+                    ui.label(egui::RichText::new("· Révisions du jour").weak());
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if let Some(remaining) = self.quiz_remaining() {
+                        countdown_label(ui, "Quiz", remaining);
+                    }
+                    if timed_out {
+                        ui.colored_label(egui::Color32::RED, "Temps écoulé");
+                    } else if let Some(remaining) = self.question_remaining(self.current_question_index) {
+                        countdown_label(ui, "Question", remaining);
+                    }
+                });
+            });
             ui.add_space(10.0);
         });
         
         egui::CentralPanel::default().show_inside(ui, |ui| {
-            if let Some(current_quiz) = self.questions.get(self.current_question_index) {
-                // Zone pour la question (ne prend que l'espace nécessaire)
-                ui.vertical(|ui| {
-                    ui.label(&current_quiz.question);
-                    ui.add_space(15.0);
-                });
-                
-                // Restaurer la réponse précédemment sélectionnée
-                if self.selected_answer.is_none() {
-                    if let Some(Some(saved_letter)) = self.user_answers.get(self.current_question_index) {
-                        for (i, answer) in current_quiz.answers.iter().enumerate() {
-                            if &answer.letter == saved_letter {
-                                self.selected_answer = Some(i);
-                                break;
-                            }
-                        }
-                    }
-                }
-                
-                // Calculer la taille disponible et la diviser
-                let available_width = ui.available_width();
-                let available_height = ui.available_height();
-                let spacing = 10.0;
-                let cell_width = (available_width - spacing) / 2.0;
-                
-                // Calculer le nombre de lignes nécessaires
-                let num_answers = current_quiz.answers.len();
-                let num_rows = (num_answers + 1) / 2;
-                let cell_height = if num_rows > 0 {
-                    (available_height - (spacing * (num_rows - 1) as f32)) / num_rows as f32
-                } else {
-                    available_height
-                };
-                
-                // Grille avec cellules de taille fixe
-                egui::Grid::new("answers_grid")
-                    .num_columns(2)
-                    .spacing([spacing, spacing])
-                    .show(ui, |ui| {
-                        for (i, answer) in current_quiz.answers.iter().enumerate() {
-                            let is_selected = self.selected_answer == Some(i);
-                            
-                            // Frame cliquable pour chaque réponse avec taille fixe
-                            let (rect, frame_resp) = ui.allocate_exact_size(
-                                egui::vec2(cell_width, cell_height),
-                                egui::Sense::click()
-                            );
-                            
-                            if frame_resp.clicked() {
-                                self.selected_answer = Some(i);
-                                self.user_answers[self.current_question_index] = Some(answer.letter.clone());
-                            }
-                            
-                            // Dessiner le cadre de la cellule
-                            let mut child_ui = ui.new_child(egui::UiBuilder::new().max_rect(rect));
-                            
-                            egui::Frame::new()
-                                .fill(if is_selected { 
-                                    child_ui.style().visuals.selection.bg_fill 
-                                } else { 
-                                    child_ui.style().visuals.widgets.inactive.bg_fill 
-                                })
-                                .stroke(if is_selected {
-                                    egui::Stroke::new(2.0, child_ui.style().visuals.selection.stroke.color)
-                                } else {
-                                    egui::Stroke::new(1.0, child_ui.style().visuals.widgets.inactive.bg_stroke.color)
-                                })
-                                .corner_radius(5.0)
-                                .inner_margin(10.0)
-                                .show(&mut child_ui, |ui| {
-                                    ui.vertical_centered(|ui| {
-                                        // Image si disponible
-                                        if let Some(image_path) = &answer.image {
-                                            // Charger depuis le cache ou lire depuis le disque
-                                            let bytes_opt = if let Some(cached) = self.image_cache.get(image_path) {
-                                                Some(cached.clone())
-                                            } else {
-                                                match std::fs::read(image_path) {
-                                                    Ok(bytes) => {
-                                                        self.image_cache.insert(image_path.clone(), bytes.clone());
-                                                        Some(bytes)
-                                                    }
-                                                    Err(_) => None,
-                                                }
-                                            };
-                                            if let Some(bytes) = bytes_opt {
-                                                // Calculer la taille max pour l'image (en laissant de l'espace pour le texte)
-                                                let max_image_size = egui::vec2(
-                                                    cell_width - 40.0, 
-                                                    cell_height - 80.0
-                                                );
-                                                ui.add(
-                                                    egui::Image::from_bytes(format!("bytes://{}", image_path), bytes)
-                                                        .max_size(max_image_size)
-                                                        .maintain_aspect_ratio(true)
-                                                        .shrink_to_fit()
-                                                        .corner_radius(5.0)
-                                                        .show_loading_spinner(true)
-                                                        .alt_text(answer.text.clone())
-                                                );
-                                            } else {
-                                                ui.label("(image manquante)");
-                                            }
-                                        } else {
-                                            ui.label(
-                                                egui::RichText::new(format!("{}) {}", answer.letter, answer.text))
-                                                    .color(if is_selected {
-                                                        ui.style().visuals.strong_text_color()
-                                                    } else {
-                                                        ui.style().visuals.text_color()
-                                                    })
-                                            );
-                                        }
-                                    });
-                                });
-                            
-                            if i % 2 == 1 { ui.end_row(); }
-                        }
-                    });
-            }
+            let index = self.current_question_index;
+            let locked = self.session_mode == SessionMode::Practice && self.checked.get(index).copied().unwrap_or(false);
+            // Temps écoulé : la réponse reste visible mais ne peut plus être modifiée
+            ui.add_enabled_ui(!timed_out, |ui| self.show_question(ui, index, locked));
         });
         
         // Footer avec les boutons de navigation (seulement si plusieurs questions ou quiz)
@@ -206,11 +921,8 @@ impl MyApp {
                 ui.add_space(5.0);
                 ui.horizontal(|ui| {
                     // Bouton "Précédent" seulement si pas la première question
-                    if self.current_question_index > 0 {
-                        if ui.button("◀ Précédent").clicked() {
-                            self.current_question_index -= 1;
-                            self.selected_answer = None;
-                        }
+                    if self.current_question_index > 0 && ui.button("◀ Précédent").clicked() {
+                        self.current_question_index -= 1;
                     }
                     
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
@@ -218,12 +930,9 @@ impl MyApp {
                         if self.current_question_index < self.questions.len() - 1 {
                             if ui.button("Suivant ▶").clicked() {
                                 self.current_question_index += 1;
-                                self.selected_answer = None;
-                            }
-                        } else {
-                            if ui.button("Terminer").clicked() {
-                                self.show_results = true;
                             }
+                        } else if ui.button("Terminer").clicked() {
+                            self.finish_session();
                         }
                     });
                 });
@@ -236,7 +945,7 @@ impl MyApp {
                 ui.horizontal(|ui| {
                     ui.with_layout(egui::Layout::centered_and_justified(egui::Direction::LeftToRight), |ui| {
                         if ui.button("Terminer").clicked() {
-                            self.show_results = true;
+                            self.finish_session();
                         }
                     });
                 });
@@ -245,62 +954,622 @@ impl MyApp {
         }
     }
     
-    fn show_results_screen(&mut self, ui: &mut egui::Ui) {
-        ui.heading("Résultats du Quiz");
-        ui.add_space(20.0);
-        
-        let mut correct_count = 0;
-        let total = self.questions.len();
-        
-        for (i, quiz) in self.questions.iter().enumerate() {
-            if let Some(Some(user_answer)) = self.user_answers.get(i) {
-                if user_answer == &quiz.correct_answer {
-                    correct_count += 1;
-                }
+    /// Énoncé et zone de réponse d'une question. Verrouillée, la question est en lecture seule et
+    /// affiche sa correction (mode entraînement après vérification, revue des réponses).
+    fn show_question(&mut self, ui: &mut egui::Ui, index: usize, locked: bool) {
+        let Some(current_quiz) = self.questions.get(index) else { return };
+        // Zone pour la question (ne prend que l'espace nécessaire)
+        ui.vertical(|ui| {
+            ui.label(&current_quiz.question);
+            if let Some(image_path) = &current_quiz.image {
+                ui.add_space(5.0);
+                question_image(
+                    ui,
+                    image_path,
+                    current_quiz.caption.as_deref(),
+                    &mut self.image_cache,
+                    &mut self.lightbox,
+                );
             }
-        }
-        
-        ui.label(format!("Score: {}/{}", correct_count, total));
-        ui.label(format!("Pourcentage: {:.1}%", (correct_count as f32 / total as f32) * 100.0));
-        ui.add_space(30.0);
+            ui.add_space(15.0);
+        });
         
-        ui.label("Détails:");
-        ui.add_space(10.0);
+        let practice = self.session_mode == SessionMode::Practice;
         
-        egui::ScrollArea::vertical().show(ui, |ui| {
-            for (i, quiz) in self.questions.iter().enumerate() {
-                let user_answer = self.user_answers.get(i).and_then(|a| a.as_ref());
-                let is_correct = user_answer.map_or(false, |a| a == &quiz.correct_answer);
-                
+        // Panneaux du bas réservés avant de dimensionner la zone de réponse
+        let current_answer = self.user_answers[index].as_ref();
+        let answered = current_answer.is_some_and(|a| !a.is_empty());
+        if locked {
+            let score = question_score(current_quiz, current_answer, &self.scoring);
+            egui::TopBottomPanel::bottom("practice_feedback").show_inside(ui, |ui| {
+                ui.add_space(5.0);
                 ui.horizontal(|ui| {
-                    ui.label(format!("Q{}: ", i + 1));
-                    if is_correct {
-                        ui.colored_label(egui::Color32::GREEN, "[✓] Correct");
-                    } else {
-                        ui.colored_label(egui::Color32::RED, "[✗] Incorrect");
-                        if let Some(ans) = user_answer {
-                            ui.label(format!("(Votre réponse: {}, Correcte: {})", ans, quiz.correct_answer));
-                        } else {
-                            ui.label(format!("(Non répondu, Correcte: {})", quiz.correct_answer));
-                        }
+                    score_label(ui, score);
+                    if score < 1.0 {
+                        ui.label(format!("Bonne réponse : {}", current_quiz.correct_answer_label()));
                     }
                 });
+                if let Some(study) = &mut self.study
+                    && score >= 1.0
+                {
+                    ui.horizontal(|ui| {
+                        ui.label("Confiance :");
+                        for confidence in Confidence::ALL {
+                            let selected = study.confidence[index] == Some(confidence);
+                            if ui.selectable_label(selected, confidence.label()).clicked() {
+                                study.confidence[index] = Some(confidence);
+                                study.grade(index, score);
+                            }
+                        }
+                    });
+                }
+                answer_feedback(ui, current_quiz, current_answer);
+                ui.add_space(5.0);
+            });
+        } else if practice && answered && current_quiz.kind() != QuestionKind::Single {
+            egui::TopBottomPanel::bottom("practice_check").show_inside(ui, |ui| {
+                ui.add_space(5.0);
+                if ui.button("✔ Vérifier").clicked() {
+                    self.checked[index] = true;
+                }
                 ui.add_space(5.0);
-            }
-        });
-        
-        // Footer pour le bouton "Choisir un autre quiz"
-        if self.quiz_files.len() >= 1 {
-            egui::TopBottomPanel::bottom("results_footer").show_inside(ui, |ui| {
-                ui.add_space(5.0);
+            });
+        } else if self.immediate_feedback
+            && let Some(answer) = current_answer.filter(|a| !a.is_empty())
+            && has_feedback(current_quiz, Some(answer))
+        {
+            egui::TopBottomPanel::bottom("immediate_feedback").show_inside(ui, |ui| {
+                ui.add_space(5.0);
+                answer_feedback(ui, current_quiz, Some(answer));
+                ui.add_space(5.0);
+            });
+        }
+        
+        let is_multiple = current_quiz.kind() == QuestionKind::Multiple;
+        if is_multiple {
+            ui.label(egui::RichText::new("Plusieurs réponses possibles").italics());
+            ui.add_space(5.0);
+        }
+        
+        let kind = current_quiz.kind();
+        if kind == QuestionKind::Hotspot && locked {
+            // Question vérifiée : montrer les zones justes à la place de l'image cliquable
+            hotspot_review(ui, current_quiz, self.user_answers[index].as_ref(), &mut self.image_cache);
+        } else if !matches!(kind, QuestionKind::Single | QuestionKind::Multiple) {
+            ui.add_enabled_ui(!locked, |ui| {
+                let slot = &mut self.user_answers[index];
+                match kind {
+                    QuestionKind::Text => text_answer_input(ui, slot, "Votre réponse"),
+                    QuestionKind::Numeric => {
+                        text_answer_input(ui, slot, "Valeur numérique");
+                        if let Some(numeric) = &current_quiz.numeric
+                            && let Some(unit) = &numeric.unit
+                        {
+                            let mut units = vec![unit.as_str()];
+                            units.extend(numeric.units.keys().map(String::as_str));
+                            ui.label(egui::RichText::new(format!("Unités acceptées : {}", units.join(", "))).weak());
+                        }
+                    }
+                    QuestionKind::Ordering => ordering_input(ui, current_quiz, slot),
+                    QuestionKind::Matching => {
+                        matching_input(ui, current_quiz, slot, self.shuffle_seed, &mut self.image_cache)
+                    }
+                    QuestionKind::Hotspot => hotspot_input(ui, current_quiz, slot, &mut self.image_cache),
+                    QuestionKind::Single | QuestionKind::Multiple => {}
+                }
+            });
+        } else {
+            // Calculer la taille disponible et la diviser
+            let available_width = ui.available_width();
+            let available_height = ui.available_height();
+            let spacing = 10.0;
+            let cell_width = (available_width - spacing) / 2.0;
+        
+            // Calculer le nombre de lignes nécessaires
+            let num_answers = current_quiz.answers.len();
+            let num_rows = num_answers.div_ceil(2);
+            let cell_height = if num_rows > 0 {
+                (available_height - (spacing * (num_rows - 1) as f32)) / num_rows as f32
+            } else {
+                available_height
+            };
+        
+            // Grille avec cellules de taille fixe
+            egui::Grid::new("answers_grid")
+                .num_columns(2)
+                .spacing([spacing, spacing])
+                .show(ui, |ui| {
+                    for (i, answer) in current_quiz.answers.iter().enumerate() {
+                        let is_selected = matches!(
+                            self.user_answers.get(index),
+                            Some(Some(UserAnswer::Choices(letters))) if letters.contains(&answer.letter)
+                        );
+                    
+                        // Frame cliquable pour chaque réponse avec taille fixe
+                        let (rect, frame_resp) = ui.allocate_exact_size(
+                            egui::vec2(cell_width, cell_height),
+                            egui::Sense::click()
+                        );
+                    
+                        if frame_resp.clicked() && !locked {
+                            let slot = &mut self.user_answers[index];
+                            if is_multiple {
+                                // Cocher / décocher la réponse
+                                let mut letters = match slot.take() {
+                                    Some(UserAnswer::Choices(letters)) => letters,
+                                    _ => Vec::new(),
+                                };
+                                if is_selected {
+                                    letters.retain(|l| l != &answer.letter);
+                                } else {
+                                    letters.push(answer.letter.clone());
+                                    letters.sort();
+                                }
+                                *slot = (!letters.is_empty()).then_some(UserAnswer::Choices(letters));
+                            } else {
+                                *slot = Some(UserAnswer::Choices(vec![answer.letter.clone()]));
+                                // En entraînement, une réponse unique est vérifiée dès le clic
+                                self.checked[index] = practice;
+                            }
+                        }
+                    
+                        // Dessiner le cadre de la cellule
+                        let mut child_ui = ui.new_child(egui::UiBuilder::new().max_rect(rect));
+                    
+                        // Une fois vérifiée : bonnes réponses en vert, mauvais choix en rouge
+                        let verdict_color = if !locked {
+                            None
+                        } else if current_quiz.correct_answer.letters().contains(&answer.letter.as_str()) {
+                            Some(egui::Color32::GREEN)
+                        } else if is_selected {
+                            Some(egui::Color32::RED)
+                        } else {
+                            None
+                        };
+                        
+                        egui::Frame::new()
+                            .fill(if let Some(color) = verdict_color {
+                                color.gamma_multiply(0.3)
+                            } else if is_selected { 
+                                child_ui.style().visuals.selection.bg_fill 
+                            } else { 
+                                child_ui.style().visuals.widgets.inactive.bg_fill 
+                            })
+                            .stroke(if let Some(color) = verdict_color {
+                                egui::Stroke::new(2.0, color)
+                            } else if is_selected {
+                                egui::Stroke::new(2.0, child_ui.style().visuals.selection.stroke.color)
+                            } else {
+                                egui::Stroke::new(1.0, child_ui.style().visuals.widgets.inactive.bg_stroke.color)
+                            })
+                            .corner_radius(5.0)
+                            .inner_margin(10.0)
+                            .show(&mut child_ui, |ui| {
+                                ui.vertical_centered(|ui| {
+                                    // Image si disponible
+                                    if let Some(image_path) = &answer.image {
+                                        if let Some(bytes) = load_image_bytes(&mut self.image_cache, image_path) {
+                                            // Calculer la taille max pour l'image (en laissant de l'espace pour le texte)
+                                            let max_image_size = egui::vec2(
+                                                cell_width - 40.0, 
+                                                cell_height - 80.0
+                                            );
+                                            ui.add(
+                                                egui::Image::from_bytes(format!("bytes://{}", image_path), bytes)
+                                                    .max_size(max_image_size)
+                                                    .maintain_aspect_ratio(true)
+                                                    .shrink_to_fit()
+                                                    .corner_radius(5.0)
+                                                    .show_loading_spinner(true)
+                                                    .alt_text(answer.text.clone())
+                                            );
+                                        } else {
+                                            ui.label("(image manquante)");
+                                        }
+                                    } else {
+                                        let checkbox = match (is_multiple, is_selected) {
+                                            (false, _) => "",
+                                            (true, false) => "☐ ",
+                                            (true, true) => "☑ ",
+                                        };
+                                        ui.label(
+                                            egui::RichText::new(format!(
+                                                "{}{}) {}",
+                                                checkbox,
+                                                current_quiz.display_letter(&answer.letter),
+                                                answer.text
+                                            ))
+                                                .color(if is_selected {
+                                                    ui.style().visuals.strong_text_color()
+                                                } else {
+                                                    ui.style().visuals.text_color()
+                                                })
+                                        );
+                                    }
+                                });
+                            });
+                    
+                        if i % 2 == 1 { ui.end_row(); }
+                    }
+                });
+        }
+    }
+    
+    /// Note de chaque question, entre 0.0 et 1.0.
+    fn question_scores(&self) -> Vec<f32> {
+        self.questions
+            .iter()
+            .enumerate()
+            .map(|(i, quiz)| {
+                let answer = self.user_answers.get(i).and_then(|a| a.as_ref());
+                question_score(quiz, answer, &self.scoring)
+            })
+            .collect()
+    }
+    
+    fn show_results_screen(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Résultats du Quiz");
+        ui.add_space(20.0);
+        
+        let scores = self.question_scores();
+        let session = session_score(&self.questions, &self.user_answers, &self.scoring);
+        
+        ui.label(format!("Score: {}/{}", format_points(session.points), format_points(session.max_points)));
+        ui.label(format!("Pourcentage: {:.1}%", session.percent()));
+        if let (Some(passed), Some(mark)) = (session.passed, self.scoring.pass_mark) {
+            let (color, verdict) = if passed {
+                (egui::Color32::GREEN, "Réussi")
+            } else {
+                (egui::Color32::RED, "Échoué")
+            };
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(verdict).strong().color(color));
+                ui.label(egui::RichText::new(format!("(seuil : {} %)", format_points(mark))).weak());
+            });
+        }
+        ui.label(format!("Temps total: {}", format_duration(self.elapsed)));
+        if self.shuffle != ShuffleMode::None {
+            ui.label(egui::RichText::new(format!("Mélange : graine {}", self.shuffle_seed)).weak());
+        }
+        let mistakes = scores.iter().any(|score| *score < 1.0);
+        if let Some(mastery) = &self.mastery {
+            ui.add_space(10.0);
+            if mistakes {
+                ui.label(format!("Refaire les erreurs : tour {}", mastery.round));
+            } else {
+                ui.colored_label(
+                    egui::Color32::GREEN,
+                    format!("Toutes les questions sont maîtrisées en {} tours", mastery.round),
+                );
+                let passes: Vec<String> = mastery
+                    .passes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, passes)| format!("Q{} : {}", i + 1, passes))
+                    .collect();
+                ui.label(format!("Passages nécessaires par question : {}", passes.join(", ")));
+            }
+        }
+        ui.add_space(30.0);
+        
+        ui.label("Détails:");
+        ui.add_space(10.0);
+        
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::CollapsingHeader::new("📈 Graphiques").show(ui, |ui| {
+                let key = self.quiz_key();
+                let attempts: Vec<&Attempt> = self.history.iter().filter(|attempt| attempt.quiz_key() == key).collect();
+                if !attempts.is_empty() {
+                    charts::score_over_time(ui, "results_scores", &attempts, self.scoring.pass_mark);
+                    charts::correctness_per_question(ui, "results_correctness", &attempts);
+                }
+                let seconds: Vec<f64> = self.time_spent.iter().map(Duration::as_secs_f64).collect();
+                charts::time_per_question(ui, "results_time", &seconds);
+            });
+            ui.add_space(10.0);
+            
+            for (i, quiz) in self.questions.iter().enumerate() {
+                let user_answer = self.user_answers.get(i).and_then(|a| a.as_ref());
+                let score = scores[i];
+                
                 ui.horizontal(|ui| {
-                    if ui.button("◀ Choisir un autre quiz").clicked() {
-                        self.show_quiz_selection = true;
-                        self.show_results = false;
+                    ui.label(format!("Q{}: ", self.question_number(i)));
+                    score_label(ui, score);
+                    let earned = question_points(quiz, user_answer, &self.scoring);
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "{}/{} pt · ⏱ {}",
+                            format_points(earned),
+                            format_points(quiz.points()),
+                            format_duration(self.time_spent[i])
+                        ))
+                        .weak(),
+                    );
+                    // La saisie reste visible à côté des réponses acceptées, même juste
+                    let typed = matches!(quiz.kind(), QuestionKind::Text | QuestionKind::Numeric);
+                    if score < 1.0 || typed {
+                        let correct_label = match quiz.kind() {
+                            QuestionKind::Text => "Acceptées",
+                            QuestionKind::Numeric | QuestionKind::Ordering | QuestionKind::Matching => "Attendue",
+                            _ => "Correcte",
+                        };
+                        match user_answer.filter(|ans| !ans.is_empty()) {
+                            Some(ans) => ui.label(format!(
+                                "(Votre réponse: {}, {}: {})",
+                                quiz.answer_label(ans),
+                                correct_label,
+                                quiz.correct_answer_label()
+                            )),
+                            None => ui.label(format!("(Non répondu, {}: {})", correct_label, quiz.correct_answer_label())),
+                        };
                     }
                 });
+                if has_feedback(quiz, user_answer) {
+                    ui.indent(("feedback", i), |ui| answer_feedback(ui, quiz, user_answer));
+                }
+                if quiz.kind() == QuestionKind::Hotspot {
+                    ui.collapsing(format!("Voir l'image (Q{})", self.question_number(i)), |ui| {
+                        hotspot_review(ui, quiz, user_answer, &mut self.image_cache);
+                    });
+                }
                 ui.add_space(5.0);
+            }
+        });
+        
+        // Footer pour les boutons "Choisir un autre quiz" et "Revoir les questions"
+        egui::TopBottomPanel::bottom("results_footer").show_inside(ui, |ui| {
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                if !self.quiz_files.is_empty() && ui.button("◀ Choisir un autre quiz").clicked() {
+                    self.show_quiz_selection = true;
+                    self.show_results = false;
+                }
+                if ui.button("📜 Historique").clicked() {
+                    self.show_history = true;
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if mistakes && self.study.is_none() && ui.button("🔁 Refaire les erreurs").clicked() {
+                        self.retry_mistakes();
+                    }
+                    if ui.button("🔍 Revoir les questions").clicked() {
+                        self.review = Some(Review { position: 0, only_mistakes: false });
+                    }
+                });
             });
+            ui.add_space(5.0);
+        });
+    }
+    
+    /// Revue question par question, en lecture seule, avec la réponse donnée et la correction.
+    fn show_review_screen(&mut self, ui: &mut egui::Ui) {
+        let scores = self.question_scores();
+        let numbers: Vec<usize> = (0..self.questions.len()).map(|i| self.question_number(i)).collect();
+        let total = self.mastery.as_ref().map_or(self.questions.len(), |mastery| mastery.all_questions.len());
+        let Some(review) = &mut self.review else { return };
+        let reviewed: Vec<usize> = (0..self.questions.len())
+            .filter(|&i| !review.only_mistakes || scores[i] < 1.0)
+            .collect();
+        review.position = review.position.min(reviewed.len().saturating_sub(1));
+        let mut back_to_results = false;
+        
+        egui::TopBottomPanel::top("review_header").show_inside(ui, |ui| {
+            ui.add_space(10.0);
+            ui.heading("Revue des réponses");
+            ui.horizontal(|ui| {
+                if let Some(&index) = reviewed.get(review.position) {
+                    ui.label(format!("Question {}/{}", numbers[index], total));
+                    if review.only_mistakes {
+                        ui.label(
+                            egui::RichText::new(format!("· erreur {}/{}", review.position + 1, reviewed.len())).weak(),
+                        );
+                    }
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.checkbox(&mut review.only_mistakes, "Seulement les erreurs et non répondues").changed() {
+                        review.position = 0;
+                    }
+                });
+            });
+            ui.add_space(10.0);
+        });
+        
+        egui::TopBottomPanel::bottom("review_footer").show_inside(ui, |ui| {
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                if review.position > 0 && ui.button("◀ Précédent").clicked() {
+                    review.position -= 1;
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if review.position + 1 < reviewed.len() && ui.button("Suivant ▶").clicked() {
+                        review.position += 1;
+                    }
+                    if ui.button("Retour aux résultats").clicked() {
+                        back_to_results = true;
+                    }
+                });
+            });
+            ui.add_space(5.0);
+        });
+        
+        let current = reviewed.get(review.position).copied();
+        egui::CentralPanel::default().show_inside(ui, |ui| match current {
+            Some(index) => self.show_question(ui, index, true),
+            None => {
+                ui.label("Aucune erreur ni question sans réponse : tout est juste !");
+            }
+        });
+        
+        if back_to_results {
+            self.review = None;
+        }
+    }
+    
+    fn show_lightbox(&mut self, ctx: &egui::Context) {
+        let Some(lightbox) = &mut self.lightbox else { return };
+        let Some(bytes) = load_image_bytes(&mut self.image_cache, &lightbox.image) else {
+            self.lightbox = None;
+            return;
+        };
+
+        let size = ctx.content_rect().size() * 0.9;
+        let mut close = false;
+        let modal = egui::Modal::new(egui::Id::new("question_image_lightbox")).show(ctx, |ui| {
+            ui.set_width(size.x);
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Molette : zoom · Glisser : déplacer · Double-clic : réinitialiser").weak());
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    close = ui.button("✕ Fermer").clicked();
+                });
+            });
+            ui.add_space(5.0);
+            ui.allocate_ui(egui::vec2(size.x, size.y - 40.0), |ui| {
+                let scene = egui::Scene::new()
+                    .zoom_range(0.1..=10.0)
+                    .show(ui, &mut lightbox.scene_rect, |ui| {
+                        ui.add(
+                            egui::Image::from_bytes(format!("bytes://{}", lightbox.image), bytes)
+                                .fit_to_original_size(1.0),
+                        );
+                    });
+                if scene.response.double_clicked() {
+                    // Un rectangle nul fait recadrer la scène sur l'image entière
+                    lightbox.scene_rect = egui::Rect::ZERO;
+                }
+            });
+        });
+        if close || modal.should_close() {
+            self.lightbox = None;
+        }
+    }
+    
+    /// Sessions terminées, de la plus récente à la plus ancienne.
+    fn show_history_screen(&mut self, ui: &mut egui::Ui) {
+        egui::TopBottomPanel::top("history_header").show_inside(ui, |ui| {
+            ui.add_space(10.0);
+            ui.heading("Historique");
+            ui.label(format!("{} session(s) terminée(s)", self.history.len()));
+            ui.add_space(10.0);
+        });
+        
+        egui::TopBottomPanel::bottom("history_footer").show_inside(ui, |ui| {
+            ui.add_space(5.0);
+            if ui.button("◀ Retour").clicked() {
+                self.show_history = false;
+            }
+            ui.add_space(5.0);
+        });
+        
+        egui::CentralPanel::default().show_inside(ui, |ui| {
+            if self.history.is_empty() {
+                ui.label("Aucune session terminée pour l'instant.");
+                return;
+            }
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                egui::CollapsingHeader::new("📈 Progression par quiz").show(ui, |ui| {
+                    let mut quiz_keys: Vec<&str> = Vec::new();
+                    for attempt in &self.history {
+                        if !quiz_keys.contains(&attempt.quiz_key()) {
+                            quiz_keys.push(attempt.quiz_key());
+                        }
+                    }
+                    for key in quiz_keys {
+                        let attempts: Vec<&Attempt> = self.history.iter().filter(|attempt| attempt.quiz_key() == key).collect();
+                        if let Some(latest) = attempts.last() {
+                            ui.label(egui::RichText::new(attempt_title(latest)).strong());
+                        }
+                        charts::score_over_time(ui, ("history_scores", key), &attempts, None);
+                        charts::correctness_per_question(ui, ("history_correctness", key), &attempts);
+                        ui.add_space(10.0);
+                    }
+                });
+                ui.add_space(10.0);
+                
+                for (i, attempt) in self.history.iter().enumerate().rev() {
+                    ui.horizontal(|ui| {
+                        ui.label(attempt.date.format("%d/%m/%Y %H:%M").to_string());
+                        ui.label(egui::RichText::new(attempt_title(attempt)).strong());
+                        ui.label(format!(
+                            "{}/{} ({:.0} %)",
+                            format_points(attempt.points),
+                            format_points(attempt.max_points),
+                            attempt.percent()
+                        ));
+                        match attempt.passed {
+                            Some(true) => ui.colored_label(egui::Color32::GREEN, "Réussi"),
+                            Some(false) => ui.colored_label(egui::Color32::RED, "Échoué"),
+                            None => ui.label(""),
+                        };
+                        let total = Duration::from_secs_f64(attempt.total_seconds());
+                        ui.label(egui::RichText::new(format!("⏱ {}", format_duration(total))).weak());
+                    });
+                    egui::CollapsingHeader::new("Détail des réponses")
+                        .id_salt(("attempt", i))
+                        .show(ui, |ui| {
+                            if let Some(seed) = attempt.shuffle_seed {
+                                ui.label(egui::RichText::new(format!("Mélange : graine {}", seed)).weak());
+                            }
+                            for (n, record) in attempt.questions.iter().enumerate() {
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("Q{}: ", n + 1));
+                                    score_label(ui, record.score);
+                                    match &record.answer {
+                                        Some(answer) => ui.label(format!("(Votre réponse: {})", answer)),
+                                        None => ui.label("(Non répondu)"),
+                                    };
+                                    let spent = Duration::from_secs_f64(record.seconds);
+                                    ui.label(egui::RichText::new(format!("⏱ {}", format_duration(spent))).weak());
+                                })
+                                .response
+                                .on_hover_text(&record.question);
+                            }
+                        });
+                    ui.add_space(5.0);
+                }
+            });
+        });
+    }
+    
+    fn show_resume_prompt(&mut self, ctx: &egui::Context) {
+        let Some(prompt) = &self.resume_prompt else { return };
+        let mut resume = false;
+        let mut restart = false;
+        let modal = egui::Modal::new(egui::Id::new("resume_prompt")).show(ctx, |ui| {
+            ui.heading("Session inachevée");
+            ui.add_space(5.0);
+            let answered = prompt
+                .saved
+                .questions
+                .iter()
+                .filter(|q| q.answer.as_ref().is_some_and(|a| !a.is_empty()))
+                .count();
+            ui.label(format!(
+                "{} réponse(s) sur {} questions, arrêt à la question {}.",
+                answered,
+                prompt.saved.questions.len(),
+                prompt.saved.current_question + 1
+            ));
+            if let Err(reason) = &prompt.mapping {
+                ui.colored_label(egui::Color32::RED, format!("Reprise impossible : {}.", reason));
+            }
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                resume = ui.add_enabled(prompt.mapping.is_ok(), egui::Button::new("▶ Reprendre")).clicked();
+                restart = ui.button("Recommencer").clicked();
+            });
+        });
+
+        if resume
+            && let Some(prompt) = self.resume_prompt.take()
+            && let Ok(mapping) = &prompt.mapping
+        {
+            self.resume_session(prompt.saved, mapping);
+        } else if restart {
+            // Nouvelle session, avec un nouveau mélange
+            session::remove(&self.quiz_key());
+            self.load_quiz_path(self.quiz_path.clone());
+        } else if modal.should_close() {
+            // Fermée sans choix, la session enregistrée reste proposée jusqu'à la première nouvelle réponse
+            self.resume_prompt = None;
         }
     }
     
@@ -308,31 +1577,652 @@ impl MyApp {
         ui.heading("Sélection du Quiz");
         ui.add_space(20.0);
         
-        ui.label("Choisissez un quiz :");
-        ui.add_space(15.0);
-        
+        ui.horizontal(|ui| {
+            ui.label("Choisissez un quiz :");
+            if ui.small_button("⟳ Actualiser").clicked() {
+                self.quiz_files = load_quiz_list(&self.quizzes_dir);
+                self.refresh_quiz_statuses();
+            }
+            if ui.small_button("📜 Historique").clicked() {
+                self.show_history = true;
+            }
+            let study_button = egui::Button::new(format!("🧠 Révisions du jour ({})", self.study_due)).small();
+            if ui.add_enabled(self.study_due > 0, study_button).clicked() {
+                self.start_study();
+            }
+        });
+        ui.add_space(10.0);
+        self.show_library_filter(ui);
+        ui.add_space(10.0);
+
+        let visible = self.visible_quizzes();
+        if self.library_filter.is_active() {
+            ui.label(egui::RichText::new(format!("{} quiz sur {}", visible.len(), self.quiz_files.len())).weak());
+            if visible.is_empty() {
+                ui.label("Aucun quiz ne correspond à la recherche.");
+            }
+        }
+        let library = Category::from_files(&visible);
+        let mut selected = None;
         egui::ScrollArea::vertical().show(ui, |ui| {
-            for quiz_file in &self.quiz_files.clone() {
-                let display_name = quiz_file.trim_end_matches(".txt").trim_end_matches(".yaml");
-                if ui.button(display_name).clicked() {
-                    self.load_selected_quiz(quiz_file);
+            self.show_category(ui, &library, Path::new(""), &mut selected);
+        });
+        if let Some(relative) = selected {
+            self.load_selected_quiz(&relative);
+        }
+    }
+
+    /// Barre de recherche, filtres et tri de l'écran de sélection.
+    fn show_library_filter(&mut self, ui: &mut egui::Ui) {
+        // Seules les valeurs présentes dans la bibliothèque sont proposées
+        let mut tags = BTreeSet::new();
+        let mut difficulties = BTreeSet::new();
+        let mut languages = BTreeSet::new();
+        for entry in self.library.values() {
+            tags.extend(entry.metadata.tags.iter().cloned());
+            difficulties.extend(entry.metadata.difficulty);
+            languages.extend(entry.metadata.language.iter().cloned());
+        }
+
+        let filter = &mut self.library_filter;
+        ui.add(
+            egui::TextEdit::singleline(&mut filter.search)
+                .hint_text("🔍 Rechercher (titre, tags, questions)")
+                .desired_width(f32::INFINITY),
+        );
+        ui.horizontal_wrapped(|ui| {
+            filter_combo(ui, "library_tag", "Tag", &mut filter.tag, tags);
+            filter_combo(ui, "library_difficulty", "Difficulté", &mut filter.difficulty, difficulties);
+            filter_combo(ui, "library_language", "Langue", &mut filter.language, languages);
+            ui.checkbox(&mut filter.never_attempted, "Jamais tenté");
+            ui.checkbox(&mut filter.failed_last, "Échoué la dernière fois");
+            if ui.add_enabled(filter.is_active(), egui::Button::new("Effacer").small()).clicked() {
+                *filter = LibraryFilter { sort: filter.sort, ..LibraryFilter::default() };
+            }
+            ui.separator();
+            egui::ComboBox::from_id_salt("library_sort")
+                .selected_text(format!("Trier par : {}", filter.sort.label()))
+                .show_ui(ui, |ui| {
+                    for sort in LibrarySort::ALL {
+                        ui.selectable_value(&mut filter.sort, sort, sort.label());
+                    }
+                });
+        });
+    }
+
+    /// Quiz de la liste retenus par la recherche et les filtres, dans l'ordre de tri choisi.
+    fn visible_quizzes(&self) -> Vec<PathBuf> {
+        let filter = &self.library_filter;
+        let search = search_key(&filter.search);
+        let mut visible: Vec<(&PathBuf, &LibraryEntry)> = self
+            .quiz_files
+            .iter()
+            .filter_map(|relative| Some((relative, self.library.get(relative)?)))
+            .filter(|(_, entry)| filter.matches(entry, &search))
+            .collect();
+        match filter.sort {
+            LibrarySort::Name => visible.sort_by(|a, b| a.1.sort_name.cmp(&b.1.sort_name)),
+            LibrarySort::LastPlayed => {
+                visible.sort_by_key(|(_, entry)| std::cmp::Reverse(entry.stats.as_ref().map(|stats| stats.last_played)))
+            }
+            LibrarySort::BestScore => {
+                let best = |entry: &LibraryEntry| entry.stats.as_ref().map_or(-1.0, |stats| stats.best);
+                visible.sort_by(|a, b| best(b.1).total_cmp(&best(a.1)));
+            }
+        }
+        visible.into_iter().map(|(relative, _)| relative.clone()).collect()
+    }
+
+    /// Quiz d'une catégorie, précédés de ses sous-catégories repliables (toutes ouvertes pendant une recherche).
+    fn show_category(&self, ui: &mut egui::Ui, category: &Category, path: &Path, selected: &mut Option<PathBuf>) {
+        let open = self.library_filter.is_active().then_some(true);
+        for (name, subcategory) in &category.subcategories {
+            let path = path.join(name);
+            egui::CollapsingHeader::new(format!("📁 {} ({})", name, subcategory.quiz_count()))
+                .id_salt(&path)
+                .open(open)
+                .show(ui, |ui| self.show_category(ui, subcategory, &path, selected));
+        }
+        for relative in &category.quizzes {
+            self.show_quiz_entry(ui, relative, selected);
+            ui.add_space(5.0);
+        }
+    }
+
+    fn show_quiz_entry(&self, ui: &mut egui::Ui, relative: &Path, selected: &mut Option<PathBuf>) {
+        let Some(entry) = self.library.get(relative) else { return };
+        let display_name = &entry.name;
+        match &entry.status {
+            QuizStatus::Invalid(issues) => {
+                // Un quiz incohérent ne peut pas être passé : on l'affiche désactivé avec ses problèmes
+                let details = issues
+                    .iter()
+                    .filter(|issue| issue.severity == Severity::Error)
+                    .map(|issue| issue.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                ui.add_enabled(false, egui::Button::new(format!("⚠ {}", display_name)))
+                    .on_disabled_hover_text(details);
+            }
+            QuizStatus::Unreadable(message) => {
+                let button = egui::Button::new(
+                    egui::RichText::new(format!("⚠ {}", display_name)).color(egui::Color32::RED),
+                );
+                if ui.add(button).on_hover_text(message).clicked() {
+                    *selected = Some(relative.to_path_buf());
                 }
-                ui.add_space(5.0);
+            }
+            QuizStatus::Valid => {
+                ui.horizontal(|ui| {
+                    let button = ui.button(display_name);
+                    let button = if entry.summary.is_empty() { button } else { button.on_hover_text(&entry.summary) };
+                    if button.clicked() {
+                        *selected = Some(relative.to_path_buf());
+                    }
+                    if let Some(stats) = &entry.stats {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "Meilleur {:.0} % · Dernier {:.0} % · Moyenne {:.0} % ({} essai{})",
+                                stats.best,
+                                stats.last,
+                                stats.average,
+                                stats.attempts,
+                                if stats.attempts > 1 { "s" } else { "" }
+                            ))
+                            .weak(),
+                        );
+                    }
+                    if entry.has_session {
+                        ui.label(egui::RichText::new("⏸ session inachevée").weak());
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// Nom d'un quiz dans l'écran de sélection : son titre, ou son fichier sans extension.
+fn display_name(relative: &Path, metadata: &QuizMetadata) -> String {
+    metadata
+        .title
+        .clone()
+        .unwrap_or_else(|| relative.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default())
+}
+
+/// Liste déroulante d'un filtre : « tous », ou l'une des valeurs proposées.
+fn filter_combo<T: Clone + PartialEq + std::fmt::Display>(
+    ui: &mut egui::Ui,
+    id: &str,
+    label: &str,
+    current: &mut Option<T>,
+    values: impl IntoIterator<Item = T>,
+) {
+    let selected = current.as_ref().map_or_else(|| "tous".to_string(), T::to_string);
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(format!("{} : {}", label, selected))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(current, None, "tous");
+            for value in values {
+                let text = value.to_string();
+                ui.selectable_value(current, Some(value), text);
             }
         });
+}
+
+/// Nom d'un quiz dans l'historique : son titre, ou son fichier sans extension.
+fn attempt_title(attempt: &Attempt) -> &str {
+    attempt.quiz_title.as_deref().unwrap_or_else(|| {
+        std::path::Path::new(&attempt.quiz_path)
+            .file_stem()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&attempt.quiz_path)
+    })
+}
+
+/// Verdict d'une question : correct, partiel ou incorrect.
+fn score_label(ui: &mut egui::Ui, score: f32) {
+    if score >= 1.0 {
+        ui.colored_label(egui::Color32::GREEN, "[✓] Correct");
+    } else if score > 0.0 {
+        ui.colored_label(
+            egui::Color32::from_rgb(255, 165, 0),
+            format!("[~] Partiel ({:.0}%)", score * 100.0),
+        );
+    } else {
+        ui.colored_label(egui::Color32::RED, "[✗] Incorrect");
+    }
+}
+
+/// Durée au format « m:ss ».
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Décompte affiché dans l'en-tête, en rouge pendant les dix dernières secondes.
+fn countdown_label(ui: &mut egui::Ui, label: &str, remaining: Duration) {
+    // Arrondi à la seconde supérieure : « 0:00 » n'apparaît qu'une fois le temps écoulé
+    let shown = Duration::from_secs(remaining.as_secs_f32().ceil() as u64);
+    let text = egui::RichText::new(format!("⏱ {} {}", label, format_duration(shown))).monospace();
+    if remaining < Duration::from_secs(10) {
+        ui.label(text.color(egui::Color32::RED));
+    } else {
+        ui.label(text);
+    }
+}
+
+/// Commentaires des réponses choisies par l'utilisateur.
+fn chosen_feedbacks<'a>(quiz: &'a Quiz, answer: Option<&UserAnswer>) -> Vec<(&'a str, &'a str)> {
+    let Some(UserAnswer::Choices(letters)) = answer else {
+        return Vec::new();
+    };
+    quiz.answers
+        .iter()
+        .filter(|a| letters.contains(&a.letter))
+        .filter_map(|a| a.feedback.as_deref().map(|feedback| (quiz.display_letter(&a.letter), feedback)))
+        .collect()
+}
+
+fn has_feedback(quiz: &Quiz, answer: Option<&UserAnswer>) -> bool {
+    quiz.explanation.is_some() || !chosen_feedbacks(quiz, answer).is_empty()
+}
+
+/// Commentaires des réponses choisies puis explication de la question.
+fn answer_feedback(ui: &mut egui::Ui, quiz: &Quiz, answer: Option<&UserAnswer>) {
+    for (letter, feedback) in chosen_feedbacks(quiz, answer) {
+        ui.label(format!("{} : {}", letter, feedback));
+    }
+    if let Some(explanation) = &quiz.explanation {
+        ui.label(egui::RichText::new(format!("Explication : {}", explanation)).italics());
+    }
+}
+
+/// Charge une image depuis le cache ou, à défaut, depuis le disque.
+fn load_image_bytes(cache: &mut HashMap<String, Vec<u8>>, image_path: &str) -> Option<Vec<u8>> {
+    if let Some(cached) = cache.get(image_path) {
+        return Some(cached.clone());
+    }
+    let bytes = std::fs::read(image_path).ok()?;
+    cache.insert(image_path.to_string(), bytes.clone());
+    Some(bytes)
+}
+
+/// Image illustrant la question, cliquable pour l'ouvrir en plein écran.
+fn question_image(
+    ui: &mut egui::Ui,
+    image_path: &str,
+    caption: Option<&str>,
+    image_cache: &mut HashMap<String, Vec<u8>>,
+    lightbox: &mut Option<Lightbox>,
+) {
+    ui.vertical_centered(|ui| {
+        match load_image_bytes(image_cache, image_path) {
+            Some(bytes) => {
+                let max_size = egui::vec2(ui.available_width(), ui.available_height() * 0.35);
+                let response = ui
+                    .add(
+                        egui::Image::from_bytes(format!("bytes://{}", image_path), bytes)
+                            .max_size(max_size)
+                            .maintain_aspect_ratio(true)
+                            .shrink_to_fit()
+                            .corner_radius(5.0)
+                            .show_loading_spinner(true)
+                            .sense(egui::Sense::click()),
+                    )
+                    .on_hover_cursor(egui::CursorIcon::ZoomIn)
+                    .on_hover_text("Cliquer pour agrandir");
+                if response.clicked() {
+                    *lightbox = Some(Lightbox {
+                        image: image_path.to_string(),
+                        scene_rect: egui::Rect::ZERO,
+                    });
+                }
+            }
+            None => {
+                ui.label("(image manquante)");
+            }
+        }
+        if let Some(caption) = caption {
+            ui.label(egui::RichText::new(caption).italics().weak());
+        }
+    });
+}
+
+/// Champ de saisie d'une question à réponse libre ou numérique.
+fn text_answer_input(ui: &mut egui::Ui, slot: &mut Option<UserAnswer>, hint: &str) {
+    let mut text = match slot {
+        Some(UserAnswer::Text(text)) => text.clone(),
+        _ => String::new(),
+    };
+    ui.add_space(10.0);
+    let response = ui.add(
+        egui::TextEdit::singleline(&mut text)
+            .hint_text(hint)
+            .desired_width(f32::INFINITY),
+    );
+    if response.changed() {
+        *slot = Some(UserAnswer::Text(text));
+    }
+}
+
+/// Liste à réordonner par glisser-déposer (ou avec les flèches) pour une question de remise en ordre.
+fn ordering_input(ui: &mut egui::Ui, quiz: &Quiz, slot: &mut Option<UserAnswer>) {
+    // Tant qu'aucun élément n'a été déplacé ni l'ordre affiché validé, la question reste sans réponse
+    let mut order = match slot {
+        Some(UserAnswer::Order(order)) => order.clone(),
+        _ => quiz.answers.iter().map(|a| a.letter.clone()).collect(),
+    };
+    let mut moved: Option<(usize, usize)> = None;
+
+    ui.label(egui::RichText::new("Faites glisser les éléments pour les remettre dans l'ordre").italics());
+    ui.add_space(5.0);
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for (pos, letter) in order.iter().enumerate() {
+            let text = quiz
+                .answers
+                .iter()
+                .find(|a| &a.letter == letter)
+                .map_or("", |a| a.text.as_str());
+
+            ui.horizontal(|ui| {
+                if ui.add_enabled(pos > 0, egui::Button::new("▲").small()).clicked() {
+                    moved = Some((pos, pos - 1));
+                }
+                if ui.add_enabled(pos + 1 < order.len(), egui::Button::new("▼").small()).clicked() {
+                    moved = Some((pos, pos + 2));
+                }
+
+                let response = ui
+                    .dnd_drag_source(egui::Id::new(("ordering_item", pos)), pos, |ui| {
+                        egui::Frame::group(ui.style()).show(ui, |ui| {
+                            ui.set_width(ui.available_width());
+                            ui.label(format!("☰  {}. {}", pos + 1, text));
+                        });
+                    })
+                    .response;
+
+                // Ligne d'insertion au-dessus ou en dessous de l'élément survolé
+                if let (Some(pointer), Some(_)) =
+                    (ui.input(|i| i.pointer.interact_pos()), response.dnd_hover_payload::<usize>())
+                {
+                    let rect = response.rect;
+                    let insert_after = pointer.y > rect.center().y;
+                    let y = if insert_after { rect.bottom() } else { rect.top() };
+                    ui.painter()
+                        .hline(rect.x_range(), y, egui::Stroke::new(2.0, ui.visuals().selection.stroke.color));
+
+                    if let Some(from) = response.dnd_release_payload::<usize>() {
+                        moved = Some((*from, if insert_after { pos + 1 } else { pos }));
+                    }
+                }
+            });
+        }
+    });
+
+    // `to` est une position d'insertion dans la liste avant retrait de l'élément
+    if let Some((from, to)) = moved {
+        let item = order.remove(from);
+        order.insert(if to > from { to - 1 } else { to }, item);
+        *slot = Some(UserAnswer::Order(order));
+    } else if slot.is_none() {
+        ui.add_space(5.0);
+        if ui
+            .button("✔ Valider cet ordre")
+            .on_hover_text("Répondre avec l'ordre affiché, sans rien déplacer")
+            .clicked()
+        {
+            *slot = Some(UserAnswer::Order(order));
+        }
+    }
+}
+
+/// Deux colonnes à relier : cliquer un élément de gauche puis un élément de droite.
+fn matching_input(
+    ui: &mut egui::Ui,
+    quiz: &Quiz,
+    slot: &mut Option<UserAnswer>,
+    seed: u64,
+    image_cache: &mut HashMap<String, Vec<u8>>,
+) {
+    let mut pairs = match slot {
+        Some(UserAnswer::Pairs(pairs)) => pairs.clone(),
+        _ => BTreeMap::new(),
+    };
+    // Élément de gauche en attente d'association (état d'interface, pas une réponse)
+    let selection_id = egui::Id::new(("matching_selection", &quiz.question));
+    let mut selected: Option<String> = ui.data(|d| d.get_temp(selection_id));
+    let mut changed = false;
+
+    // Colonne de droite mélangée selon la graine de la session, comme les réponses (voir `shuffle_answers`)
+    let right_order = shuffled_indices(quiz.matches.len(), seed ^ seed_from_str(&quiz.question));
+
+    ui.label(egui::RichText::new("Cliquez un élément à gauche puis son correspondant à droite").italics());
+    ui.add_space(5.0);
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        ui.columns(2, |columns| {
+            for answer in &quiz.answers {
+                let partner = pairs
+                    .get(&answer.letter)
+                    .and_then(|letter| quiz.matches.iter().find(|m| &m.letter == letter));
+                let suffix = partner.map(|m| format!("  →  {}", m.text)).unwrap_or_default();
+                let is_selected = selected.as_ref() == Some(&answer.letter);
+                if matching_cell(&mut columns[0], answer, &suffix, is_selected, image_cache).clicked() {
+                    if is_selected {
+                        selected = None;
+                    } else if pairs.remove(&answer.letter).is_some() {
+                        // Cliquer un élément déjà associé défait l'association
+                        changed = true;
+                    } else {
+                        selected = Some(answer.letter.clone());
+                    }
+                }
+            }
+
+            for &i in &right_order {
+                let item = &quiz.matches[i];
+                let linked: Vec<&str> = pairs
+                    .iter()
+                    .filter(|(_, right)| *right == &item.letter)
+                    .map(|(left, _)| left.as_str())
+                    .collect();
+                let suffix = if linked.is_empty() { String::new() } else { format!("  ←  {}", linked.join(", ")) };
+                if matching_cell(&mut columns[1], item, &suffix, false, image_cache).clicked()
+                    && let Some(left) = selected.take()
+                {
+                    pairs.insert(left, item.letter.clone());
+                    changed = true;
+                }
+            }
+        });
+    });
+
+    ui.data_mut(|d| match &selected {
+        Some(letter) => d.insert_temp(selection_id, letter.clone()),
+        None => d.remove::<String>(selection_id),
+    });
+    if changed {
+        *slot = (!pairs.is_empty()).then_some(UserAnswer::Pairs(pairs));
+    }
+}
+
+/// Cellule cliquable d'une colonne d'association (texte et image éventuelle).
+fn matching_cell(
+    ui: &mut egui::Ui,
+    item: &Answer,
+    suffix: &str,
+    is_selected: bool,
+    image_cache: &mut HashMap<String, Vec<u8>>,
+) -> egui::Response {
+    let frame = egui::Frame::new()
+        .fill(if is_selected {
+            ui.style().visuals.selection.bg_fill
+        } else {
+            ui.style().visuals.widgets.inactive.bg_fill
+        })
+        .corner_radius(5.0)
+        .inner_margin(8.0)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            if let Some(image_path) = &item.image {
+                match load_image_bytes(image_cache, image_path) {
+                    Some(bytes) => {
+                        ui.add(
+                            egui::Image::from_bytes(format!("bytes://{}", image_path), bytes)
+                                .max_height(80.0)
+                                .maintain_aspect_ratio(true)
+                                .corner_radius(5.0)
+                                .alt_text(item.text.clone()),
+                        );
+                    }
+                    None => {
+                        ui.label("(image manquante)");
+                    }
+                }
+            }
+            ui.label(format!("{}) {}{}", item.letter, item.text, suffix));
+        });
+    ui.add_space(5.0);
+    frame.response.interact(egui::Sense::click())
+}
+
+/// Grande image sur laquelle cliquer pour une question « cliquez au bon endroit ».
+fn hotspot_input(
+    ui: &mut egui::Ui,
+    quiz: &Quiz,
+    slot: &mut Option<UserAnswer>,
+    image_cache: &mut HashMap<String, Vec<u8>>,
+) {
+    let Some(hotspot) = &quiz.hotspot else { return };
+    ui.label(egui::RichText::new("Cliquez à l'endroit demandé sur l'image").italics());
+    ui.add_space(5.0);
+
+    let Some(response) = hotspot_image(ui, hotspot, image_cache, ui.available_size(), egui::Sense::click()) else {
+        return;
+    };
+    if response.clicked()
+        && let Some(pos) = response.interact_pointer_pos()
+    {
+        *slot = Some(UserAnswer::Point(to_relative(response.rect, pos)));
+    }
+    if let Some(UserAnswer::Point(point)) = slot {
+        paint_click(ui.painter(), response.rect, *point, ui.visuals().selection.stroke.color);
+    }
+}
+
+/// Image, zones justes et clic de l'utilisateur, affichés dans les résultats.
+fn hotspot_review(
+    ui: &mut egui::Ui,
+    quiz: &Quiz,
+    answer: Option<&UserAnswer>,
+    image_cache: &mut HashMap<String, Vec<u8>>,
+) {
+    let Some(hotspot) = &quiz.hotspot else { return };
+    let Some(response) = hotspot_image(ui, hotspot, image_cache, egui::vec2(400.0, 300.0), egui::Sense::hover()) else {
+        return;
+    };
+    let painter = ui.painter_at(response.rect);
+    for region in &hotspot.regions {
+        paint_region(&painter, response.rect, region, egui::Color32::GREEN);
+    }
+    if let Some(UserAnswer::Point(point)) = answer {
+        let hit = hotspot.regions.iter().any(|region| region.contains(*point));
+        let color = if hit { egui::Color32::GREEN } else { egui::Color32::RED };
+        paint_click(&painter, response.rect, *point, color);
+    }
+}
+
+fn hotspot_image(
+    ui: &mut egui::Ui,
+    hotspot: &Hotspot,
+    image_cache: &mut HashMap<String, Vec<u8>>,
+    max_size: egui::Vec2,
+    sense: egui::Sense,
+) -> Option<egui::Response> {
+    let Some(bytes) = load_image_bytes(image_cache, &hotspot.image) else {
+        ui.label("(image manquante)");
+        return None;
+    };
+    Some(
+        ui.add(
+            egui::Image::from_bytes(format!("bytes://{}", hotspot.image), bytes)
+                .max_size(max_size)
+                .maintain_aspect_ratio(true)
+                .shrink_to_fit()
+                .show_loading_spinner(true)
+                .sense(sense),
+        ),
+    )
+}
+
+fn to_relative(rect: egui::Rect, pos: egui::Pos2) -> [f32; 2] {
+    [
+        ((pos.x - rect.left()) / rect.width()).clamp(0.0, 1.0),
+        ((pos.y - rect.top()) / rect.height()).clamp(0.0, 1.0),
+    ]
+}
+
+fn to_screen(rect: egui::Rect, [x, y]: [f32; 2]) -> egui::Pos2 {
+    rect.lerp_inside(egui::vec2(x, y))
+}
+
+fn paint_click(painter: &egui::Painter, rect: egui::Rect, point: [f32; 2], color: egui::Color32) {
+    let center = to_screen(rect, point);
+    painter.circle_stroke(center, 8.0, egui::Stroke::new(3.0, color));
+    painter.circle_filled(center, 2.5, color);
+}
+
+fn paint_region(painter: &egui::Painter, rect: egui::Rect, region: &Region, color: egui::Color32) {
+    let stroke = egui::Stroke::new(2.0, color);
+    let fill = color.gamma_multiply(0.25);
+    match region {
+        Region::Rect { x, y, width, height } => {
+            let area = egui::Rect::from_min_max(to_screen(rect, [*x, *y]), to_screen(rect, [x + width, y + height]));
+            painter.rect(area, 0.0, fill, stroke, egui::StrokeKind::Middle);
+        }
+        Region::Circle { x, y, radius } => {
+            // Ellipse approchée par un polygone, le rayon étant relatif à chaque dimension
+            let points = (0..48)
+                .map(|i| {
+                    let angle = i as f32 / 48.0 * std::f32::consts::TAU;
+                    to_screen(rect, [x + radius * angle.cos(), y + radius * angle.sin()])
+                })
+                .collect();
+            painter.add(egui::Shape::convex_polygon(points, fill, stroke));
+        }
+        Region::Polygon { points } => {
+            // Contour seulement : le remplissage d'egui ne gère que les polygones convexes
+            let points = points.iter().map(|p| to_screen(rect, *p)).collect();
+            painter.add(egui::Shape::closed_line(points, stroke));
+        }
     }
 }
 
 impl eframe::App for MyApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.apply_file_changes(ctx);
         egui::CentralPanel::default().show(ctx, |ui| {
-            if self.show_quiz_selection {
+            if self.show_history {
+                self.show_history_screen(ui);
+            } else if self.show_quiz_selection {
                 self.show_quiz_selection_screen(ui);
+            } else if self.has_blocking_problem() {
+                self.show_load_error_screen(ui);
+            } else if self.review.is_some() {
+                self.show_review_screen(ui);
             } else if self.show_results {
                 self.show_results_screen(ui);
             } else {
                 self.show_quiz_screen(ui);
+                self.grade_study();
+                self.autosave();
+                return;
             }
+            // Chronomètre à l'arrêt en dehors de l'écran des questions
+            self.last_tick = None;
         });
+        self.show_lightbox(ctx);
+        self.show_resume_prompt(ctx);
     }
 }
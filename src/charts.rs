@@ -0,0 +1,92 @@
+use crate::history::Attempt;
+use eframe::egui;
+use egui_plot::{Bar, BarChart, Line, Plot, PlotPoints, Points};
+
+const CHART_HEIGHT: f32 = 160.0;
+
+/// Graphique figé : pas de zoom ni de déplacement, pour ne pas capturer le défilement de la page.
+fn static_plot(id: impl std::hash::Hash) -> Plot<'static> {
+    Plot::new(id)
+        .height(CHART_HEIGHT)
+        .allow_zoom(false)
+        .allow_drag(false)
+        .allow_scroll(false)
+        .allow_boxed_zoom(false)
+        .allow_double_click_reset(false)
+}
+
+/// Score (en %) de chaque session terminée d'un quiz, dans l'ordre chronologique.
+pub fn score_over_time(ui: &mut egui::Ui, id: impl std::hash::Hash, attempts: &[&Attempt], pass_mark: Option<f32>) {
+    let scores: Vec<[f64; 2]> = attempts
+        .iter()
+        .enumerate()
+        .map(|(i, attempt)| [(i + 1) as f64, f64::from(attempt.percent())])
+        .collect();
+
+    ui.label("Score par session (%)");
+    static_plot(id)
+        .include_x(1.0)
+        .include_y(0.0)
+        .include_y(100.0)
+        .x_axis_label("Session")
+        .show(ui, |plot_ui| {
+            if let Some(mark) = pass_mark {
+                let (last, mark) = (attempts.len().max(1) as f64, f64::from(mark));
+                let threshold = PlotPoints::new(vec![[1.0, mark], [last, mark]]);
+                plot_ui.line(Line::new("Seuil de réussite", threshold).color(egui::Color32::GRAY));
+            }
+            plot_ui.line(Line::new("Score", PlotPoints::new(scores.clone())));
+            plot_ui.points(Points::new("Score", PlotPoints::new(scores)).radius(3.0));
+        });
+}
+
+/// Réussite moyenne (en %) de chaque question sur toutes les sessions d'un quiz. Les questions sont
+/// reconnues à leur identifiant ou leur énoncé, l'ordre pouvant changer d'une session à l'autre.
+pub fn correctness_per_question(ui: &mut egui::Ui, id: impl std::hash::Hash, attempts: &[&Attempt]) {
+    let Some(latest) = attempts.last() else { return };
+    let bars: Vec<Bar> = latest
+        .questions
+        .iter()
+        .enumerate()
+        .map(|(i, record)| {
+            let scores: Vec<f32> = attempts
+                .iter()
+                .flat_map(|attempt| attempt.questions.iter())
+                .filter(|other| other.same_question(record))
+                .map(|other| other.score)
+                .collect();
+            let average = scores.iter().sum::<f32>() / scores.len().max(1) as f32 * 100.0;
+            Bar::new((i + 1) as f64, f64::from(average))
+                .name(format!("Q{} : {}", i + 1, record.question))
+                .fill(bar_color(average))
+        })
+        .collect();
+
+    ui.label("Réussite par question, toutes sessions confondues (%)");
+    static_plot(id)
+        .include_y(0.0)
+        .include_y(100.0)
+        .x_axis_label("Question")
+        .show(ui, |plot_ui| plot_ui.bar_chart(BarChart::new("Réussite", bars)));
+}
+
+/// Temps passé (en secondes) sur chaque question d'une session.
+pub fn time_per_question(ui: &mut egui::Ui, id: impl std::hash::Hash, seconds: &[f64]) {
+    let bars: Vec<Bar> = seconds
+        .iter()
+        .enumerate()
+        .map(|(i, secs)| Bar::new((i + 1) as f64, *secs).name(format!("Q{}", i + 1)))
+        .collect();
+
+    ui.label("Temps passé par question (s)");
+    static_plot(id)
+        .include_y(0.0)
+        .x_axis_label("Question")
+        .show(ui, |plot_ui| plot_ui.bar_chart(BarChart::new("Temps", bars)));
+}
+
+/// Du rouge (0 %) au vert (100 %).
+fn bar_color(percent: f32) -> egui::Color32 {
+    let t = (percent / 100.0).clamp(0.0, 1.0);
+    egui::Color32::from_rgb((220.0 * (1.0 - t)) as u8, (180.0 * t) as u8, 60)
+}
@@ -0,0 +1,278 @@
+use crate::config::AppConfig;
+use crate::quiz_loader::{load_quiz_file, load_quiz_list};
+use crate::validation::{validate_quiz_file, Severity};
+use serde::Serialize;
+
+const USAGE: &str = "\
+Usage : quiz-gui <validate|lint> [DOSSIER] [--format text|json] [--deny-warnings]
+
+Vérifie tous les quiz de DOSSIER (par défaut `quiz_path` de Config.yaml) sans ouvrir de fenêtre.
+Code de sortie : 0 si tout est valide, 1 si des erreurs sont trouvées, 2 en cas d'usage incorrect.";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+struct Options {
+    dir: Option<String>,
+    format: Format,
+    deny_warnings: bool,
+}
+
+#[derive(Serialize)]
+struct Diagnostic {
+    severity: Severity,
+    /// Numéro (1-based) de la question concernée
+    #[serde(skip_serializing_if = "Option::is_none")]
+    question: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<usize>,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct FileReport {
+    path: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Serialize)]
+struct Report {
+    files: Vec<FileReport>,
+    errors: usize,
+    warnings: usize,
+}
+
+/// Indique si le premier argument de la ligne de commande est une sous-commande headless.
+pub fn is_command(arg: &str) -> bool {
+    matches!(arg, "validate" | "lint")
+}
+
+/// Exécute la sous-commande et retourne le code de sortie du processus.
+pub fn run(args: &[String], config: &AppConfig) -> i32 {
+    #[cfg(windows)]
+    attach_parent_console();
+
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return 0;
+    }
+    let options = match parse_args(&args[1..]) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            return 2;
+        }
+    };
+
+    let dir = options.dir.clone().unwrap_or_else(|| config.quiz_path.clone());
+    if !std::path::Path::new(&dir).is_dir() {
+        eprintln!("Dossier introuvable : {}", dir);
+        return 2;
+    }
+
+    let report = lint_directory(&dir);
+    match options.format {
+        Format::Text => print_text(&report),
+        Format::Json => match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{}", json),
+            Err(err) => {
+                eprintln!("Impossible de sérialiser le rapport : {}", err);
+                return 2;
+            }
+        },
+    }
+
+    if report.errors > 0 || (options.deny_warnings && report.warnings > 0) {
+        1
+    } else {
+        0
+    }
+}
+
+/// En release, le binaire Windows n'a pas de console (`windows_subsystem = "windows"`) : sans elle,
+/// la sortie de la sous-commande serait perdue. On reprend donc celle du terminal qui l'a lancé.
+#[cfg(windows)]
+fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    // Échoue sans conséquence si le processus a déjà une console (build debug) ou si le parent n'en a pas
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options { dir: None, format: Format::Text, deny_warnings: false };
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--format" => {
+                options.format = match iter.next().map(String::as_str) {
+                    Some("text") => Format::Text,
+                    Some("json") => Format::Json,
+                    other => return Err(format!("Format inconnu : {}", other.unwrap_or("(absent)"))),
+                }
+            }
+            "--json" => options.format = Format::Json,
+            "--deny-warnings" => options.deny_warnings = true,
+            flag if flag.starts_with('-') => return Err(format!("Option inconnue : {}", flag)),
+            path if options.dir.is_none() => options.dir = Some(path.to_string()),
+            extra => return Err(format!("Argument en trop : {}", extra)),
+        }
+    }
+    Ok(options)
+}
+
+fn lint_directory(dir: &str) -> Report {
+    let mut files = Vec::new();
+    for relative in load_quiz_list(dir) {
+        let path = std::path::Path::new(dir).join(relative).to_string_lossy().into_owned();
+        let diagnostics = match load_quiz_file(&path) {
+            Ok(quiz_file) => validate_quiz_file(&quiz_file)
+                .into_iter()
+                .map(|issue| Diagnostic {
+                    severity: issue.severity,
+                    question: issue.question.map(|i| i + 1),
+                    line: None,
+                    column: None,
+                    message: issue.message,
+                })
+                .collect(),
+            Err(err) => {
+                let location = err.location();
+                vec![Diagnostic {
+                    severity: Severity::Error,
+                    question: None,
+                    line: location.map(|loc| loc.line),
+                    column: location.map(|loc| loc.column),
+                    message: format!("{} : {}", err.kind_label(), err.detail()),
+                }]
+            }
+        };
+        files.push(FileReport { path, diagnostics });
+    }
+
+    let count = |severity| {
+        files
+            .iter()
+            .flat_map(|file| &file.diagnostics)
+            .filter(|d| d.severity == severity)
+            .count()
+    };
+    let errors = count(Severity::Error);
+    let warnings = count(Severity::Warning);
+    Report { files, errors, warnings }
+}
+
+fn print_text(report: &Report) {
+    for file in &report.files {
+        if file.diagnostics.is_empty() {
+            println!("ok      {}", file.path);
+            continue;
+        }
+        for d in &file.diagnostics {
+            let severity = match d.severity {
+                Severity::Error => "erreur",
+                Severity::Warning => "avertissement",
+            };
+            let mut location = file.path.clone();
+            if let (Some(line), Some(column)) = (d.line, d.column) {
+                location.push_str(&format!(":{}:{}", line, column));
+            }
+            match d.question {
+                Some(q) => println!("{}: {}: Q{} : {}", location, severity, q, d.message),
+                None => println!("{}: {}: {}", location, severity, d.message),
+            }
+        }
+    }
+    println!(
+        "\n{} fichier(s) vérifié(s) : {} erreur(s), {} avertissement(s)",
+        report.files.len(),
+        report.errors,
+        report.warnings
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    /// Dossier temporaire contenant un seul quiz.
+    fn library(name: &str, quiz: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("quiz-gui-cli-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("quiz.yaml"), quiz).unwrap();
+        dir
+    }
+
+    fn run_on(dir: &Path, extra: &[&str]) -> i32 {
+        let mut command = args(&["validate", dir.to_str().unwrap()]);
+        command.extend(args(extra));
+        run(&command, &AppConfig::default())
+    }
+
+    const VALID: &str = "questions:
+  - question: Lequel ?
+    answers: [{letter: A, text: a}, {letter: B, text: b}]
+    correct_answer: A";
+
+    #[test]
+    fn options_are_parsed() {
+        let options = parse_args(&args(&["quizzes", "--format", "json", "--deny-warnings"])).unwrap();
+        assert_eq!(options.dir.as_deref(), Some("quizzes"));
+        assert!(options.format == Format::Json);
+        assert!(options.deny_warnings);
+
+        let defaults = parse_args(&[]).unwrap();
+        assert_eq!(defaults.dir, None);
+        assert!(defaults.format == Format::Text);
+        assert!(parse_args(&args(&["--json"])).unwrap().format == Format::Json);
+    }
+
+    #[test]
+    fn bad_usage_is_rejected() {
+        assert!(parse_args(&args(&["--format", "xml"])).is_err());
+        assert!(parse_args(&args(&["--format"])).is_err());
+        assert!(parse_args(&args(&["--verbose"])).is_err());
+        assert!(parse_args(&args(&["a", "b"])).is_err());
+    }
+
+    #[test]
+    fn exit_code_reflects_errors() {
+        assert_eq!(run_on(&library("valid", VALID), &[]), 0);
+        assert_eq!(run_on(&library("invalid", &VALID.replace("correct_answer: A", "correct_answer: C")), &[]), 1);
+        assert_eq!(run_on(&library("syntax", "questions: [\n"), &["--format", "json"]), 1);
+    }
+
+    #[test]
+    fn warnings_fail_only_when_denied() {
+        let dir = library("warning", &VALID.replace(", {letter: B, text: b}", ""));
+        assert_eq!(run_on(&dir, &[]), 0);
+        assert_eq!(run_on(&dir, &["--deny-warnings"]), 1);
+    }
+
+    #[test]
+    fn usage_errors_exit_with_2() {
+        let dir = library("usage", VALID);
+        assert_eq!(run_on(&dir, &["--format", "xml"]), 2);
+        assert_eq!(run_on(&dir.join("absent"), &[]), 2);
+        assert_eq!(run(&args(&["validate", "--help"]), &AppConfig::default()), 0);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::models::{SessionMode, ShuffleMode};
 use serde::{Deserialize, Serialize};
 use std::fs;
 
@@ -7,6 +8,15 @@ pub struct AppConfig {
     pub quiz_path: String,
     pub window_width: f32,
     pub window_height: f32,
+    /// Impose le mode de session à tous les quiz, quel que soit leur fichier
+    #[serde(default)]
+    pub session_mode: Option<SessionMode>,
+    /// Impose le mélange des questions et/ou des réponses à tous les quiz
+    #[serde(default)]
+    pub shuffle: Option<ShuffleMode>,
+    /// Graine imposée au mélange, pour rejouer une session à l'identique
+    #[serde(default)]
+    pub shuffle_seed: Option<u64>,
 }
 
 impl Default for AppConfig {
@@ -16,6 +26,9 @@ impl Default for AppConfig {
             quiz_path: "Quizzes".to_string(),
             window_width: 400.0,
             window_height: 300.0,
+            session_mode: None,
+            shuffle: None,
+            shuffle_seed: None,
         }
     }
 }
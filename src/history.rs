@@ -0,0 +1,176 @@
+use crate::models::ShuffleMode;
+use crate::storage::{data_dir, data_file};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+
+/// Session terminée, conservée dans l'historique local.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Attempt {
+    pub date: DateTime<Local>,
+    /// Chemin du quiz relatif à la bibliothèque
+    pub quiz_path: String,
+    /// `metadata.id` du quiz, qui l'identifie même après un renommage du fichier
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiz_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiz_title: Option<String>,
+    pub points: f32,
+    pub max_points: f32,
+    pub passed: Option<bool>,
+    /// Mélange de la session ; avec la même graine (`shuffle_seed`), elle se rejoue à l'identique
+    #[serde(default)]
+    pub shuffle: ShuffleMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shuffle_seed: Option<u64>,
+    pub questions: Vec<QuestionRecord>,
+}
+
+/// Réponse donnée à une question lors d'une session terminée.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QuestionRecord {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub question: String,
+    /// Réponse telle qu'affichée, `None` si la question est restée sans réponse
+    pub answer: Option<String>,
+    /// Note entre 0.0 et 1.0
+    pub score: f32,
+    /// Temps passé, en secondes
+    pub seconds: f64,
+}
+
+impl QuestionRecord {
+    /// Même question d'une session à l'autre : même identifiant, ou même énoncé à défaut.
+    pub fn same_question(&self, other: &QuestionRecord) -> bool {
+        match (&self.id, &other.id) {
+            (Some(id), Some(other_id)) => id == other_id,
+            _ => self.question == other.question,
+        }
+    }
+}
+
+impl Attempt {
+    /// Clé du quiz pour regrouper ses sessions : son identifiant, ou son chemin relatif à défaut.
+    pub fn quiz_key(&self) -> &str {
+        self.quiz_id.as_deref().unwrap_or(&self.quiz_path)
+    }
+
+    pub fn percent(&self) -> f32 {
+        if self.max_points > 0.0 { self.points / self.max_points * 100.0 } else { 0.0 }
+    }
+
+    pub fn total_seconds(&self) -> f64 {
+        self.questions.iter().map(|q| q.seconds).sum()
+    }
+
+    /// Session échouée : sous le seuil de réussite du quiz, ou sous 50 % s'il n'en a pas.
+    pub fn failed(&self) -> bool {
+        self.passed.map_or(self.percent() < 50.0, |passed| !passed)
+    }
+}
+
+/// Meilleur, dernier et moyen score (en %) des sessions d'un quiz.
+pub struct QuizStats {
+    pub attempts: usize,
+    pub best: f32,
+    pub last: f32,
+    pub average: f32,
+    pub last_played: DateTime<Local>,
+    pub last_failed: bool,
+}
+
+/// Statistiques d'un quiz (voir `Attempt::quiz_key`), `None` s'il n'a jamais été terminé.
+pub fn quiz_stats(history: &[Attempt], quiz_key: &str) -> Option<QuizStats> {
+    let attempts: Vec<&Attempt> = history.iter().filter(|attempt| attempt.quiz_key() == quiz_key).collect();
+    let last = attempts.last()?;
+    let percents: Vec<f32> = attempts.iter().map(|attempt| attempt.percent()).collect();
+    Some(QuizStats {
+        attempts: attempts.len(),
+        best: percents.iter().copied().fold(0.0, f32::max),
+        last: last.percent(),
+        average: percents.iter().sum::<f32>() / percents.len() as f32,
+        last_played: last.date,
+        last_failed: last.failed(),
+    })
+}
+
+/// Une ligne JSON par session, dans l'ordre où elles ont été terminées.
+const HISTORY_FILE: &str = "history.jsonl";
+
+/// Historique complet ; les lignes illisibles sont ignorées.
+pub fn load() -> Vec<Attempt> {
+    let Some(content) = data_dir().and_then(|dir| fs::read_to_string(dir.join(HISTORY_FILE)).ok()) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+pub fn append(attempt: &Attempt) -> io::Result<()> {
+    let line = serde_json::to_string(attempt).map_err(io::Error::other)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(data_file(HISTORY_FILE)?)?;
+    writeln!(file, "{}", line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn attempt(quiz_path: &str, day: u32, points: f32, passed: Option<bool>) -> Attempt {
+        Attempt {
+            date: Local.with_ymd_and_hms(2024, 3, day, 12, 0, 0).unwrap(),
+            quiz_path: quiz_path.to_string(),
+            quiz_id: None,
+            quiz_title: None,
+            points,
+            max_points: 10.0,
+            passed,
+            shuffle: ShuffleMode::None,
+            shuffle_seed: None,
+            questions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn stats_summarize_the_sessions_of_one_quiz() {
+        let history = vec![
+            attempt("maths.yaml", 1, 4.0, None),
+            attempt("histoire.yaml", 2, 10.0, None),
+            attempt("maths.yaml", 3, 9.0, None),
+            attempt("maths.yaml", 4, 5.0, None),
+        ];
+        let stats = quiz_stats(&history, "maths.yaml").unwrap();
+        assert_eq!(stats.attempts, 3);
+        assert_eq!(stats.best, 90.0);
+        assert_eq!(stats.last, 50.0);
+        assert_eq!(stats.average, 60.0);
+        assert_eq!(stats.last_played, history[3].date);
+        assert!(!stats.last_failed);
+        assert!(quiz_stats(&history, "physique.yaml").is_none());
+    }
+
+    #[test]
+    fn failure_uses_the_pass_mark_or_half_the_points() {
+        assert!(attempt("q.yaml", 1, 4.9, None).failed());
+        assert!(!attempt("q.yaml", 1, 5.0, None).failed());
+        assert!(attempt("q.yaml", 1, 9.0, Some(false)).failed());
+        assert!(!attempt("q.yaml", 1, 2.0, Some(true)).failed());
+    }
+
+    #[test]
+    fn quiz_id_groups_sessions_across_renames() {
+        let mut renamed = attempt("nouveau.yaml", 2, 8.0, None);
+        renamed.quiz_id = Some("maths".to_string());
+        let mut original = attempt("ancien.yaml", 1, 2.0, None);
+        original.quiz_id = Some("maths".to_string());
+        let stats = quiz_stats(&[original, renamed], "maths").unwrap();
+        assert_eq!(stats.attempts, 2);
+        assert_eq!(stats.last, 80.0);
+        assert!(!stats.last_failed);
+    }
+}
@@ -2,10 +2,22 @@
 #![allow(rustdoc::missing_crate_level_docs)]
 
 mod app;
+mod charts;
+mod cli;
 mod config;
 mod fonts;
+mod history;
 mod models;
+mod numeric;
 mod quiz_loader;
+mod scoring;
+mod session;
+mod shuffle;
+mod storage;
+mod study;
+mod text_match;
+mod validation;
+mod watcher;
 
 use app::MyApp;
 use config::load_config;
@@ -14,6 +26,14 @@ use eframe::egui;
 fn main() -> eframe::Result {
     env_logger::init();
     let config = load_config();
+
+    // Sous-commandes headless (validate / lint) : pas de fenêtre
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(command) = args.first()
+        && cli::is_command(command)
+    {
+        std::process::exit(cli::run(&args, &config));
+    }
     
     // Charger l'icône depuis un fichier
     let icon_data = load_icon();
@@ -25,14 +45,13 @@ fn main() -> eframe::Result {
         ..Default::default()
     };
     let app_title = config.app_title.clone();
-    let quiz_path = config.quiz_path.clone();
     eframe::run_native(
         &app_title,
         options,
         Box::new(move |cc| {
             egui_extras::install_image_loaders(&cc.egui_ctx);
             fonts::setup_fonts(&cc.egui_ctx);
-            Ok(Box::new(MyApp::new(&quiz_path)))
+            Ok(Box::new(MyApp::new(&config, &cc.egui_ctx)))
         }),
     )
 }
@@ -41,16 +60,16 @@ fn load_icon() -> egui::IconData {
     // Charger votre fichier d'icône (PNG recommandé)
     let icon_path = "./icon.png";
     
-    if let Ok(image_data) = std::fs::read(icon_path) {
-        if let Ok(image) = image::load_from_memory(&image_data) {
-            let rgba = image.to_rgba8();
-            let (width, height) = rgba.dimensions();
-            return egui::IconData {
-                rgba: rgba.into_raw(),
-                width,
-                height,
-            };
-        }
+    if let Ok(image_data) = std::fs::read(icon_path)
+        && let Ok(image) = image::load_from_memory(&image_data)
+    {
+        let rgba = image.to_rgba8();
+        let (width, height) = rgba.dimensions();
+        return egui::IconData {
+            rgba: rgba.into_raw(),
+            width,
+            height,
+        };
     }
     
     // Icône par défaut si le chargement échoue
@@ -1,4 +1,7 @@
+use crate::numeric::describe_numeric;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Answer {
@@ -6,16 +9,545 @@ pub struct Answer {
     pub text: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub image: Option<String>,
+    /// Commentaire affiché quand cette réponse a été choisie (pourquoi ce distracteur est faux...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feedback: Option<String>,
+    /// Reste en dernière position quand les réponses sont mélangées (« Toutes les réponses »...)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+}
+
+/// Bonne réponse d'une question : une seule lettre ou une liste de lettres.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum CorrectAnswer {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Default for CorrectAnswer {
+    fn default() -> Self {
+        Self::Many(Vec::new())
+    }
+}
+
+impl CorrectAnswer {
+    pub fn letters(&self) -> Vec<&str> {
+        match self {
+            Self::One(letter) => vec![letter.as_str()],
+            Self::Many(letters) => letters.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+impl fmt::Display for CorrectAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.letters().join(", "))
+    }
+}
+
+/// Type de question. Déduit de `correct_answer` s'il n'est pas précisé dans le fichier.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestionKind {
+    /// Une seule réponse à choisir
+    Single,
+    /// « Cochez toutes les bonnes réponses »
+    Multiple,
+    /// Réponse libre saisie au clavier, comparée à `accepted_answers`
+    Text,
+    /// Valeur numérique (avec tolérance et unités), décrite par `numeric`
+    Numeric,
+    /// Remettre les réponses dans l'ordre donné par `correct_answer`
+    Ordering,
+    /// Relier chaque réponse (colonne de gauche) à un élément de `matches` (colonne de droite)
+    Matching,
+    /// Cliquer au bon endroit d'une image, décrite par `hotspot`
+    Hotspot,
+}
+
+/// Barème des questions à choix multiples.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PartialCredit {
+    /// Tous les points si la sélection est exactement la bonne, sinon zéro
+    #[default]
+    AllOrNothing,
+    /// Proportion des réponses correctement cochées ou laissées vides
+    Proportional,
+    /// (bonnes cochées - mauvaises cochées) / nombre de bonnes, minimum zéro
+    RightMinusWrong,
+}
+
+/// Barème des questions de remise en ordre.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderingCredit {
+    /// Tous les points uniquement si l'ordre est exactement le bon
+    #[default]
+    Exact,
+    /// Longueur de la plus longue sous-suite bien ordonnée / nombre d'éléments
+    LongestSubsequence,
+    /// 1 - (paires inversées / nombre total de paires), distance de Kendall
+    Kendall,
+}
+
+/// Règles de comparaison d'une réponse libre avec les réponses acceptées.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct TextMatching {
+    pub case_insensitive: bool,
+    /// « Éléphant » == « elephant »
+    pub ignore_accents: bool,
+    /// Ignorer les espaces en début/fin et les espaces multiples
+    pub trim: bool,
+    /// Nombre de fautes de frappe tolérées (distance de Levenshtein)
+    pub max_distance: usize,
+    /// Les réponses acceptées sont des expressions régulières
+    pub regex: bool,
+}
+
+impl Default for TextMatching {
+    fn default() -> Self {
+        Self {
+            case_insensitive: true,
+            ignore_accents: false,
+            trim: true,
+            max_distance: 0,
+            regex: false,
+        }
+    }
+}
+
+/// Réponse attendue d'une question numérique : une valeur avec tolérance, ou un intervalle.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct NumericAnswer {
+    /// Valeur exacte attendue, exprimée dans `unit`
+    pub value: Option<f64>,
+    /// Écart absolu toléré autour de `value`
+    pub tolerance: Option<f64>,
+    /// Écart relatif toléré autour de `value` (0.01 = 1 %)
+    pub relative_tolerance: Option<f64>,
+    /// Bornes inclusives, à la place de (ou en plus de) `value`
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// Unité de référence de la valeur attendue
+    pub unit: Option<String>,
+    /// Autres unités acceptées et leur facteur vers `unit` (ex : km: 1000 si `unit` vaut m)
+    pub units: BTreeMap<String, f64>,
+    /// Refuser une réponse saisie sans unité
+    pub require_unit: bool,
+}
+
+/// Zone d'une image, en coordonnées relatives (0.0 à 1.0 depuis le coin haut gauche).
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(tag = "shape", rename_all = "snake_case")]
+pub enum Region {
+    Rect { x: f32, y: f32, width: f32, height: f32 },
+    /// Le rayon est relatif à chaque dimension : la zone est une ellipse sur une image non carrée
+    Circle { x: f32, y: f32, radius: f32 },
+    Polygon { points: Vec<[f32; 2]> },
+}
+
+impl Region {
+    pub fn contains(&self, [px, py]: [f32; 2]) -> bool {
+        match self {
+            Self::Rect { x, y, width, height } => px >= *x && px <= x + width && py >= *y && py <= y + height,
+            Self::Circle { x, y, radius } => (px - x).powi(2) + (py - y).powi(2) <= radius.powi(2),
+            Self::Polygon { points } => {
+                // Lancer de rayon horizontal : un nombre impair d'intersections signifie « à l'intérieur »
+                let mut inside = false;
+                let mut j = points.len().wrapping_sub(1);
+                for (i, &[xi, yi]) in points.iter().enumerate() {
+                    let [xj, yj] = points[j];
+                    if (yi > py) != (yj > py) && px < (xj - xi) * (py - yi) / (yj - yi) + xi {
+                        inside = !inside;
+                    }
+                    j = i;
+                }
+                inside
+            }
+        }
+    }
+}
+
+/// Image sur laquelle cliquer et zones considérées comme justes.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Hotspot {
+    pub image: String,
+    pub regions: Vec<Region>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Quiz {
+    /// Identifiant stable de la question : l'historique, les révisions et la reprise de session
+    /// la retrouvent même si elle change de place dans le fichier
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     pub question: String,
+    /// Image illustrant la question, affichée au-dessus des réponses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// Légende affichée sous l'image de la question
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caption: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<QuestionKind>,
+    #[serde(default)]
     pub answers: Vec<Answer>,
-    pub correct_answer: String,
+    #[serde(default)]
+    pub correct_answer: CorrectAnswer,
+    /// Remplace le barème du quiz pour cette question
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partial_credit: Option<PartialCredit>,
+    /// Réponses acceptées pour une question à réponse libre
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accepted_answers: Vec<String>,
+    #[serde(default)]
+    pub matching: TextMatching,
+    /// Réponse attendue pour une question numérique
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub numeric: Option<NumericAnswer>,
+    /// Remplace le barème de remise en ordre du quiz pour cette question
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ordering_credit: Option<OrderingCredit>,
+    /// Colonne de droite d'une question d'association
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matches: Vec<Answer>,
+    /// Associations attendues : lettre de gauche -> lettre de droite
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pairs: BTreeMap<String, String>,
+    /// Image et zones justes d'une question « cliquez sur l'image »
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hotspot: Option<Hotspot>,
+    /// Explication de la bonne réponse, affichée après la question
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    /// Poids de la question dans le score (1 point par défaut)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<f32>,
+    /// Remplace la pénalité du quiz pour cette question
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub penalty: Option<f32>,
+    /// Temps accordé pour répondre, en secondes ; la question suivante s'affiche à expiration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_limit: Option<u64>,
+    /// Lettres affichées après mélange des réponses (lettre d'origine -> lettre affichée), vide sans mélange
+    #[serde(skip)]
+    pub display_letters: BTreeMap<String, String>,
+}
+
+impl Quiz {
+    pub fn kind(&self) -> QuestionKind {
+        match (self.kind, &self.correct_answer) {
+            (Some(kind), _) => kind,
+            (None, _) if self.numeric.is_some() => QuestionKind::Numeric,
+            (None, _) if !self.matches.is_empty() => QuestionKind::Matching,
+            (None, _) if self.hotspot.is_some() => QuestionKind::Hotspot,
+            (None, _) if !self.accepted_answers.is_empty() => QuestionKind::Text,
+            (None, CorrectAnswer::Many(_)) => QuestionKind::Multiple,
+            (None, CorrectAnswer::One(_)) => QuestionKind::Single,
+        }
+    }
+
+    pub fn points(&self) -> f32 {
+        self.points.unwrap_or(1.0)
+    }
+
+    /// Bonne réponse telle qu'affichée dans les résultats.
+    pub fn correct_answer_label(&self) -> String {
+        match self.kind() {
+            QuestionKind::Single | QuestionKind::Multiple => self.display_choices(&self.correct_answer.letters()),
+            QuestionKind::Ordering => self.display_order(&self.correct_answer.letters()),
+            QuestionKind::Matching => format_pairs(&self.pairs),
+            QuestionKind::Hotspot => "zone indiquée sur l'image".to_string(),
+            QuestionKind::Text => self.accepted_answers.join(" / "),
+            QuestionKind::Numeric => self.numeric.as_ref().map(describe_numeric).unwrap_or_default(),
+        }
+    }
+
+    /// Lettre sous laquelle la réponse est affichée (identique à l'originale sans mélange).
+    pub fn display_letter<'a>(&'a self, letter: &'a str) -> &'a str {
+        self.display_letters.get(letter).map_or(letter, String::as_str)
+    }
+
+    /// Réponse de l'utilisateur telle qu'affichée, avec les lettres vues à l'écran.
+    pub fn answer_label(&self, answer: &UserAnswer) -> String {
+        match answer {
+            UserAnswer::Choices(letters) => self.display_choices(letters),
+            UserAnswer::Order(letters) => self.display_order(letters),
+            _ => answer.to_string(),
+        }
+    }
+
+    fn display_choices<S: AsRef<str>>(&self, letters: &[S]) -> String {
+        let mut shown: Vec<&str> = letters.iter().map(|l| self.display_letter(l.as_ref())).collect();
+        shown.sort_unstable();
+        shown.join(", ")
+    }
+
+    /// Ordre affiché avec le texte des éléments, seul visible pendant la remise en ordre.
+    fn display_order<S: AsRef<str>>(&self, letters: &[S]) -> String {
+        let shown: Vec<&str> = letters
+            .iter()
+            .map(|l| {
+                let letter = l.as_ref();
+                self.answers
+                    .iter()
+                    .find(|a| a.letter == letter && !a.text.is_empty())
+                    .map_or_else(|| self.display_letter(letter), |a| a.text.as_str())
+            })
+            .collect();
+        shown.join(" → ")
+    }
+}
+
+/// Déroulement d'une session de quiz.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionMode {
+    /// Correction immédiate : la réponse est vérifiée et verrouillée, la bonne réponse révélée
+    Practice,
+    /// Résultats uniquement à la fin du quiz
+    #[default]
+    Exam,
+}
+
+/// Éléments mélangés au début de chaque session.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ShuffleMode {
+    /// Ordre du fichier
+    #[default]
+    None,
+    Questions,
+    Answers,
+    Both,
+}
+
+impl ShuffleMode {
+    pub fn questions(self) -> bool {
+        matches!(self, Self::Questions | Self::Both)
+    }
+
+    pub fn answers(self) -> bool {
+        matches!(self, Self::Answers | Self::Both)
+    }
+}
+
+/// Règles de notation communes à tout un fichier de quiz.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct Scoring {
+    #[serde(default)]
+    pub partial_credit: PartialCredit,
+    #[serde(default)]
+    pub ordering_credit: OrderingCredit,
+    /// Points retirés pour une réponse fausse (une question sans réponse n'est jamais pénalisée)
+    #[serde(default)]
+    pub penalty: f32,
+    /// Pourcentage minimal pour réussir le quiz
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pass_mark: Option<f32>,
+}
+
+/// Niveau de difficulté annoncé d'un quiz.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    #[serde(alias = "facile")]
+    Easy,
+    #[serde(alias = "moyen")]
+    Medium,
+    #[serde(alias = "difficile")]
+    Hard,
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Easy => "facile",
+            Self::Medium => "moyen",
+            Self::Hard => "difficile",
+        })
+    }
+}
+
+/// Informations descriptives d'un quiz, toutes facultatives.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct QuizMetadata {
+    /// Identifiant stable du quiz : l'historique et la reprise de session le suivent même si le fichier est renommé
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<Difficulty>,
+    /// Durée estimée, en minutes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_duration: Option<u32>,
+}
+
+impl QuizMetadata {
+    /// Résumé affiché au survol d'un quiz (description, auteur, difficulté...).
+    pub fn summary(&self) -> String {
+        let mut lines = Vec::new();
+        if let Some(description) = &self.description {
+            lines.push(description.clone());
+        }
+        let mut details = Vec::new();
+        if let Some(author) = &self.author {
+            details.push(format!("par {}", author));
+        }
+        if let Some(version) = &self.version {
+            details.push(format!("version {}", version));
+        }
+        if let Some(language) = &self.language {
+            details.push(format!("langue : {}", language));
+        }
+        if let Some(difficulty) = self.difficulty {
+            details.push(format!("difficulté : {}", difficulty));
+        }
+        if let Some(minutes) = self.estimated_duration {
+            details.push(format!("environ {} min", minutes));
+        }
+        if !details.is_empty() {
+            lines.push(details.join(" · "));
+        }
+        if !self.tags.is_empty() {
+            lines.push(self.tags.iter().map(|tag| format!("#{}", tag)).collect::<Vec<_>>().join(" "));
+        }
+        lines.join("\n")
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct QuizFile {
+    #[serde(default)]
+    pub metadata: QuizMetadata,
+    #[serde(default)]
+    pub scoring: Scoring,
+    /// Afficher explications et commentaires dès qu'une réponse est donnée, sans attendre la fin
+    #[serde(default)]
+    pub immediate_feedback: bool,
+    /// Mode de session par défaut de ce quiz (remplaçable par `session_mode` dans Config.yaml)
+    #[serde(default)]
+    pub mode: Option<SessionMode>,
+    /// Mélange des questions et/ou des réponses (remplaçable par `shuffle` dans Config.yaml)
+    #[serde(default)]
+    pub shuffle: Option<ShuffleMode>,
+    /// Graine du mélange pour rejouer une session à l'identique ; tirée au hasard si absente
+    #[serde(default)]
+    pub shuffle_seed: Option<u64>,
+    /// Durée du quiz en secondes ; les résultats s'affichent automatiquement à expiration
+    #[serde(default)]
+    pub time_limit: Option<u64>,
     pub questions: Vec<Quiz>,
 }
+
+/// Réponse donnée par l'utilisateur à une question.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub enum UserAnswer {
+    /// Lettres sélectionnées (une seule pour une question simple)
+    Choices(Vec<String>),
+    /// Texte saisi pour une question à réponse libre ou numérique
+    Text(String),
+    /// Lettres dans l'ordre choisi par l'utilisateur
+    Order(Vec<String>),
+    /// Associations faites par l'utilisateur : lettre de gauche -> lettre de droite
+    Pairs(BTreeMap<String, String>),
+    /// Point cliqué sur l'image, en coordonnées relatives
+    Point([f32; 2]),
+}
+
+impl UserAnswer {
+    /// Une réponse libre vide compte comme une absence de réponse.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Choices(letters) | Self::Order(letters) => letters.is_empty(),
+            Self::Text(text) => text.trim().is_empty(),
+            Self::Pairs(pairs) => pairs.is_empty(),
+            Self::Point(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for UserAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Choices(letters) => write!(f, "{}", letters.join(", ")),
+            Self::Text(text) => write!(f, "« {} »", text.trim()),
+            Self::Order(letters) => write!(f, "{}", letters.join(" → ")),
+            Self::Pairs(pairs) => write!(f, "{}", format_pairs(pairs)),
+            Self::Point([x, y]) => write!(f, "clic à ({:.0} %, {:.0} %)", x * 100.0, y * 100.0),
+        }
+    }
+}
+
+fn format_pairs(pairs: &BTreeMap<String, String>) -> String {
+    pairs
+        .iter()
+        .map(|(left, right)| format!("{}-{}", left, right))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(yaml: &str) -> Region {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn rect_includes_its_edges() {
+        let rect = region("{shape: rect, x: 0.2, y: 0.2, width: 0.5, height: 0.25}");
+        assert!(rect.contains([0.2, 0.2]));
+        assert!(rect.contains([0.7, 0.45]));
+        assert!(rect.contains([0.5, 0.3]));
+        assert!(!rect.contains([0.71, 0.3]));
+        assert!(!rect.contains([0.5, 0.1]));
+    }
+
+    #[test]
+    fn circle_uses_distance_to_center() {
+        let circle = region("{shape: circle, x: 0.5, y: 0.5, radius: 0.1}");
+        assert!(circle.contains([0.5, 0.5]));
+        assert!(circle.contains([0.55, 0.55]));
+        assert!(!circle.contains([0.58, 0.58]));
+    }
+
+    #[test]
+    fn polygon_handles_concave_shapes() {
+        // « U » : la partie creuse entre les deux branches n'est pas dans la zone
+        let u = region(
+            "{shape: polygon, points: [[0.0, 0.0], [0.3, 0.0], [0.3, 0.6], [0.6, 0.6], [0.6, 0.0], [0.9, 0.0], [0.9, 0.9], [0.0, 0.9]]}",
+        );
+        assert!(u.contains([0.1, 0.1]));
+        assert!(u.contains([0.8, 0.1]));
+        assert!(u.contains([0.45, 0.8]));
+        assert!(!u.contains([0.45, 0.3]));
+        assert!(!u.contains([0.95, 0.5]));
+    }
+
+    #[test]
+    fn triangle_and_degenerate_polygons() {
+        let triangle = region("{shape: polygon, points: [[0.6, 0.5], [0.66, 0.52], [0.63, 0.6]]}");
+        assert!(triangle.contains([0.63, 0.54]));
+        assert!(!triangle.contains([0.6, 0.6]));
+        assert!(!region("{shape: polygon, points: []}").contains([0.5, 0.5]));
+        assert!(!region("{shape: polygon, points: [[0.5, 0.5]]}").contains([0.5, 0.5]));
+    }
+}
@@ -0,0 +1,183 @@
+use crate::models::NumericAnswer;
+
+/// Lit un nombre en acceptant la virgule décimale (« 1,5 ») et les séparateurs de milliers
+/// (« 1 500 », « 1.500,5 », « 1,500.5 »).
+pub fn parse_number(text: &str) -> Option<f64> {
+    let compact: String = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '\u{202f}' && *c != '\'')
+        .collect();
+    if compact.is_empty() {
+        return None;
+    }
+
+    let normalized = match (compact.rfind(','), compact.rfind('.')) {
+        // Le dernier séparateur rencontré est le séparateur décimal
+        (Some(comma), Some(dot)) if comma > dot => compact.replace('.', "").replace(',', "."),
+        (Some(_), Some(_)) => compact.replace(',', ""),
+        (Some(_), None) => compact.replace(',', "."),
+        _ => compact,
+    };
+    normalized.parse::<f64>().ok().filter(|n| n.is_finite())
+}
+
+/// Sépare une saisie comme « 1,5 km » en valeur et unité éventuelle.
+pub fn parse_quantity(text: &str) -> Option<(f64, Option<String>)> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !(c.is_ascii_digit() || c.is_whitespace() || matches!(c, ',' | '.' | '+' | '-' | '\u{202f}' | '\'')))
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let value = parse_number(number)?;
+    let unit = unit.trim();
+    Some((value, (!unit.is_empty()).then(|| unit.to_string())))
+}
+
+/// Convertit la saisie dans l'unité de référence, `None` si l'unité n'est pas acceptée.
+fn to_reference_unit(input: &str, expected: &NumericAnswer) -> Option<f64> {
+    let (value, unit) = parse_quantity(input)?;
+    match unit {
+        None if expected.require_unit && expected.unit.is_some() => None,
+        None => Some(value),
+        Some(unit) if expected.unit.as_deref() == Some(unit.as_str()) => Some(value),
+        Some(unit) => expected.units.get(&unit).map(|factor| value * factor),
+    }
+}
+
+pub fn is_correct(input: &str, expected: &NumericAnswer) -> bool {
+    let Some(value) = to_reference_unit(input, expected) else {
+        return false;
+    };
+    // Petite marge pour absorber les erreurs d'arrondi des conversions (1.5 km -> 1500 m)
+    let epsilon = 1e-9 * value.abs().max(1.0);
+
+    if let Some(min) = expected.min
+        && value < min - epsilon
+    {
+        return false;
+    }
+    if let Some(max) = expected.max
+        && value > max + epsilon
+    {
+        return false;
+    }
+    if let Some(target) = expected.value {
+        let absolute = expected.tolerance.unwrap_or(0.0);
+        let relative = expected.relative_tolerance.unwrap_or(0.0) * target.abs();
+        return (value - target).abs() <= absolute.max(relative) + epsilon;
+    }
+    expected.min.is_some() || expected.max.is_some()
+}
+
+/// Réponse attendue sous forme lisible (« 1500 ± 5 m », « entre 2 et 3 »).
+pub fn describe_numeric(expected: &NumericAnswer) -> String {
+    let unit = expected.unit.as_ref().map(|u| format!(" {}", u)).unwrap_or_default();
+    let number = |n: f64| n.to_string();
+
+    let mut description = match (expected.value, expected.min, expected.max) {
+        (Some(value), _, _) => {
+            let mut text = number(value);
+            if let Some(tolerance) = expected.tolerance.filter(|t| *t > 0.0) {
+                text.push_str(&format!(" ± {}", number(tolerance)));
+            }
+            if let Some(relative) = expected.relative_tolerance.filter(|t| *t > 0.0) {
+                text.push_str(&format!(" ± {} %", number(relative * 100.0)));
+            }
+            text + &unit
+        }
+        (None, Some(min), Some(max)) => format!("entre {} et {}{}", number(min), number(max), unit),
+        (None, Some(min), None) => format!("au moins {}{}", number(min), unit),
+        (None, None, Some(max)) => format!("au plus {}{}", number(max), unit),
+        (None, None, None) => String::new(),
+    };
+    if !expected.units.is_empty() {
+        let units: Vec<&str> = expected.units.keys().map(String::as_str).collect();
+        description.push_str(&format!(" (aussi en {})", units.join(", ")));
+    }
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expected(yaml: &str) -> NumericAnswer {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn parses_decimal_commas_and_thousands_separators() {
+        assert_eq!(parse_number("1,5"), Some(1.5));
+        assert_eq!(parse_number("1.5"), Some(1.5));
+        assert_eq!(parse_number("1 500"), Some(1500.0));
+        assert_eq!(parse_number("1\u{202f}500,25"), Some(1500.25));
+        assert_eq!(parse_number("1,500.5"), Some(1500.5));
+        assert_eq!(parse_number("1.500,5"), Some(1500.5));
+        assert_eq!(parse_number("1'000"), Some(1000.0));
+        assert_eq!(parse_number("-2,5"), Some(-2.5));
+        assert_eq!(parse_number(""), None);
+        assert_eq!(parse_number("abc"), None);
+        assert_eq!(parse_number("inf"), None);
+    }
+
+    #[test]
+    fn splits_value_and_unit() {
+        assert_eq!(parse_quantity("1,5 km"), Some((1.5, Some("km".to_string()))));
+        assert_eq!(parse_quantity("1500m"), Some((1500.0, Some("m".to_string()))));
+        assert_eq!(parse_quantity(" 42 "), Some((42.0, None)));
+        assert_eq!(parse_quantity("km"), None);
+    }
+
+    #[test]
+    fn tolerance_is_absolute_or_relative() {
+        let absolute = expected("{value: 100, tolerance: 2}");
+        assert!(is_correct("102", &absolute));
+        assert!(is_correct("98,0", &absolute));
+        assert!(!is_correct("102.1", &absolute));
+
+        let relative = expected("{value: 200, relative_tolerance: 0.01}");
+        assert!(is_correct("202", &relative));
+        assert!(!is_correct("203", &relative));
+
+        let exact = expected("{value: 0.3}");
+        assert!(is_correct("0,3", &exact));
+        assert!(!is_correct("0,31", &exact));
+    }
+
+    #[test]
+    fn bounds_are_inclusive() {
+        let range = expected("{min: 2, max: 3}");
+        assert!(is_correct("2", &range));
+        assert!(is_correct("3", &range));
+        assert!(!is_correct("3,01", &range));
+        assert!(is_correct("10", &expected("{min: 5}")));
+        assert!(!is_correct("4", &expected("{min: 5}")));
+        // Sans valeur ni borne, rien n'est juste
+        assert!(!is_correct("1", &expected("{}")));
+    }
+
+    #[test]
+    fn converts_accepted_units() {
+        let distance = expected("{value: 1500, tolerance: 10, unit: m, units: {km: 1000}}");
+        assert!(is_correct("1500 m", &distance));
+        assert!(is_correct("1,5 km", &distance));
+        assert!(is_correct("1500", &distance));
+        assert!(!is_correct("1,5 mi", &distance));
+        assert!(!is_correct("1,5 m", &distance));
+    }
+
+    #[test]
+    fn unit_can_be_required() {
+        let distance = expected("{value: 1500, unit: m, units: {km: 1000}, require_unit: true}");
+        assert!(!is_correct("1500", &distance));
+        assert!(is_correct("1.5km", &distance));
+    }
+
+    #[test]
+    fn describes_expected_answer() {
+        assert_eq!(describe_numeric(&expected("{value: 1500, tolerance: 5, unit: m}")), "1500 ± 5 m");
+        assert_eq!(describe_numeric(&expected("{value: 200, relative_tolerance: 0.01}")), "200 ± 1 %");
+        assert_eq!(describe_numeric(&expected("{min: 2, max: 3}")), "entre 2 et 3");
+        assert_eq!(describe_numeric(&expected("{value: 1, unit: m, units: {km: 1000}}")), "1 m (aussi en km)");
+    }
+}
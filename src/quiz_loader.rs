@@ -1,56 +1,244 @@
-use crate::models::{Answer, Quiz, QuizFile};
+use crate::models::QuizFile;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::fs;
+use std::path::{Path, PathBuf};
 
-pub fn load_quizzes(path: &str) -> Vec<Quiz> {
-    let content = fs::read_to_string(path).unwrap_or_else(|_| {
-        r#"questions:
-  - question: "Question par défaut ?"
-    answers:
-      - letter: A
-        text: "Réponse A"
-      - letter: B
-        text: "Réponse B"
-      - letter: C
-        text: "Réponse C"
-      - letter: D
-        text: "Réponse D"
-    correct_answer: A
-"#.to_string()
-    });
-    
-    let quiz_file: QuizFile = serde_yaml::from_str(&content).unwrap_or_else(|_| {
-        QuizFile {
-            questions: vec![Quiz {
-                question: "Question par défaut ?".to_string(),
-                answers: vec![
-                    Answer { letter: "A".to_string(), text: "Réponse A".to_string(), image: None },
-                    Answer { letter: "B".to_string(), text: "Réponse B".to_string(), image: None },
-                    Answer { letter: "C".to_string(), text: "Réponse C".to_string(), image: None },
-                    Answer { letter: "D".to_string(), text: "Réponse D".to_string(), image: None },
-                ],
-                correct_answer: "A".to_string(),
-            }],
-        }
-    });
-    
-    quiz_file.questions
-}
-
-pub fn load_quiz_list(dir: &str) -> Vec<String> {
-    let mut quiz_files = Vec::new();
-    if let Ok(entries) = fs::read_dir(dir) {
-        for entry in entries.flatten() {
-            if let Ok(file_type) = entry.file_type() {
-                if file_type.is_file() {
-                    if let Some(name) = entry.file_name().to_str() {
-                        if name.ends_with(".txt") || name.ends_with(".yaml") {
-                            quiz_files.push(name.to_string());
-                        }
-                    }
+/// Erreur survenue lors du chargement d'un fichier de quiz.
+#[derive(Debug)]
+pub enum QuizLoadError {
+    /// Le fichier est introuvable ou illisible.
+    Io { path: String, source: std::io::Error },
+    /// Le contenu n'est pas du YAML valide.
+    Syntax { path: String, message: String, location: Option<Location> },
+    /// Le YAML est valide mais ne correspond pas au format attendu d'un quiz.
+    Schema { path: String, message: String, location: Option<Location> },
+}
+
+/// Position (1-indexée) d'une erreur dans un fichier YAML.
+#[derive(Debug, Clone, Copy)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl QuizLoadError {
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Io { path, .. } | Self::Syntax { path, .. } | Self::Schema { path, .. } => path,
+        }
+    }
+
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Self::Io { .. } => None,
+            Self::Syntax { location, .. } | Self::Schema { location, .. } => *location,
+        }
+    }
+
+    pub fn kind_label(&self) -> &'static str {
+        match self {
+            Self::Io { .. } => "Fichier illisible",
+            Self::Syntax { .. } => "YAML invalide",
+            Self::Schema { .. } => "Format de quiz invalide",
+        }
+    }
+
+    /// Message d'erreur sans le chemin ni la position.
+    pub fn detail(&self) -> String {
+        match self {
+            Self::Io { source, .. } => source.to_string(),
+            Self::Syntax { message, .. } | Self::Schema { message, .. } => message.clone(),
+        }
+    }
+
+    fn from_yaml(path: &str, err: serde_yaml::Error, schema: bool) -> Self {
+        let location = err.location().map(|loc| Location {
+            line: loc.line(),
+            column: loc.column(),
+        });
+        let path = path.to_string();
+        let message = err.to_string();
+        if schema {
+            Self::Schema { path, message, location }
+        } else {
+            Self::Syntax { path, message, location }
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ligne {}, colonne {}", self.line, self.column)
+    }
+}
+
+impl fmt::Display for QuizLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "{}: {}", path, source),
+            Self::Syntax { path, message, location } | Self::Schema { path, message, location } => {
+                write!(f, "{}: {}", path, self.kind_label())?;
+                if let Some(loc) = location {
+                    write!(f, " ({})", loc)?;
                 }
+                write!(f, ": {}", message)
             }
         }
     }
+}
+
+impl std::error::Error for QuizLoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+pub fn load_quiz_file(path: &str) -> Result<QuizFile, QuizLoadError> {
+    let content = fs::read_to_string(path).map_err(|source| QuizLoadError::Io {
+        path: path.to_string(),
+        source,
+    })?;
+
+    // Valider d'abord la syntaxe YAML seule, pour distinguer une faute de frappe
+    // d'un document bien formé mais qui ne décrit pas un quiz
+    serde_yaml::from_str::<serde_yaml::Value>(&content)
+        .map_err(|err| QuizLoadError::from_yaml(path, err, false))?;
+
+    serde_yaml::from_str(&content).map_err(|err| QuizLoadError::from_yaml(path, err, true))
+}
+
+/// Quiz de la bibliothèque `dir` et de ses sous-dossiers, en chemins relatifs à `dir`.
+/// Les fichiers et dossiers cachés (« .git »...) sont ignorés.
+pub fn load_quiz_list(dir: impl AsRef<Path>) -> Vec<PathBuf> {
+    let mut quiz_files = Vec::new();
+    collect_quiz_files(dir.as_ref(), Path::new(""), &mut quiz_files);
     quiz_files.sort();
     quiz_files
 }
+
+fn collect_quiz_files(root: &Path, relative: &Path, quiz_files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(root.join(relative)) else { return };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else { continue };
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else { continue };
+        if name.starts_with('.') {
+            continue;
+        }
+        if file_type.is_dir() {
+            collect_quiz_files(root, &relative.join(&name), quiz_files);
+        } else if file_type.is_file() && (name.ends_with(".txt") || name.ends_with(".yaml")) {
+            quiz_files.push(relative.join(name));
+        }
+    }
+}
+
+/// Dossier de la bibliothèque vu comme une catégorie : ses sous-dossiers et ses quiz.
+#[derive(Debug, Default)]
+pub struct Category {
+    pub subcategories: BTreeMap<String, Category>,
+    /// Chemins relatifs à la racine de la bibliothèque
+    pub quizzes: Vec<PathBuf>,
+}
+
+impl Category {
+    /// Arborescence des catégories d'une liste de quiz (chemins relatifs, voir `load_quiz_list`).
+    pub fn from_files<'a>(quiz_files: impl IntoIterator<Item = &'a PathBuf>) -> Self {
+        let mut root = Category::default();
+        for quiz_file in quiz_files {
+            let mut category = &mut root;
+            if let Some(parent) = quiz_file.parent() {
+                for folder in parent.iter() {
+                    let name = folder.to_string_lossy().into_owned();
+                    category = category.subcategories.entry(name).or_default();
+                }
+            }
+            category.quizzes.push(quiz_file.clone());
+        }
+        root
+    }
+
+    /// Nombre de quiz de la catégorie et de toutes ses sous-catégories.
+    pub fn quiz_count(&self) -> usize {
+        self.quizzes.len() + self.subcategories.values().map(Category::quiz_count).sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Dossier temporaire vide, propre à un test.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("quiz-gui-loader-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn load(name: &str, content: &str) -> Result<QuizFile, QuizLoadError> {
+        let path = temp_dir(name).join("quiz.yaml");
+        fs::write(&path, content).unwrap();
+        load_quiz_file(path.to_str().unwrap())
+    }
+
+    #[test]
+    fn valid_file_is_loaded() {
+        let quiz_file = load("valid", "questions:\n  - question: Q ?\n    answers: [{letter: A, text: a}]\n    correct_answer: A\n")
+            .unwrap();
+        assert_eq!(quiz_file.questions.len(), 1);
+    }
+
+    #[test]
+    fn missing_file_is_an_io_error() {
+        let path = temp_dir("missing").join("absent.yaml");
+        let err = load_quiz_file(path.to_str().unwrap()).unwrap_err();
+        assert!(matches!(err, QuizLoadError::Io { .. }));
+        assert!(err.location().is_none());
+    }
+
+    #[test]
+    fn malformed_yaml_is_a_syntax_error_with_location() {
+        let err = load("syntax", "questions:\n  - question: Q ?\n    answers: [A, B\n").unwrap_err();
+        assert!(matches!(err, QuizLoadError::Syntax { .. }), "{}", err);
+        let location = err.location().unwrap();
+        assert_eq!((location.line, location.column), (4, 1));
+    }
+
+    #[test]
+    fn well_formed_yaml_that_is_not_a_quiz_is_a_schema_error() {
+        let err = load("schema", "questions:\n  - question: Q ?\n    answers: 12\n").unwrap_err();
+        assert!(matches!(err, QuizLoadError::Schema { .. }), "{}", err);
+        let location = err.location().unwrap();
+        assert_eq!((location.line, location.column), (3, 14));
+        assert!(err.to_string().contains("ligne 3, colonne 14"));
+    }
+    #[test]
+    fn quiz_list_is_recursive_and_skips_hidden_entries() {
+        let dir = temp_dir("list");
+        for path in ["b.yaml", "a.txt", "notes.md", "Maths/Algèbre/eq.yaml", "Maths/x.yaml", ".git/c.yaml", "Maths/.d.yaml"] {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "questions: []").unwrap();
+        }
+        let expected: Vec<PathBuf> =
+            ["Maths/Algèbre/eq.yaml", "Maths/x.yaml", "a.txt", "b.yaml"].iter().map(PathBuf::from).collect();
+        assert_eq!(load_quiz_list(&dir), expected);
+    }
+
+    #[test]
+    fn categories_follow_folders() {
+        let files: Vec<PathBuf> =
+            ["a.yaml", "Maths/x.yaml", "Maths/Algèbre/eq.yaml", "Histoire/h.yaml"].iter().map(PathBuf::from).collect();
+        let root = Category::from_files(&files);
+        assert_eq!(root.quizzes, vec![PathBuf::from("a.yaml")]);
+        assert_eq!(root.subcategories.keys().collect::<Vec<_>>(), ["Histoire", "Maths"]);
+        let maths = &root.subcategories["Maths"];
+        assert_eq!(maths.quizzes, vec![PathBuf::from("Maths/x.yaml")]);
+        assert_eq!(maths.quiz_count(), 2);
+        assert_eq!(root.quiz_count(), 4);
+    }
+}
@@ -0,0 +1,391 @@
+use crate::models::{OrderingCredit, PartialCredit, QuestionKind, Quiz, Scoring, UserAnswer};
+use crate::numeric;
+use crate::text_match::is_accepted;
+
+/// Note obtenue à une question, entre 0.0 (faux ou non répondu) et 1.0 (entièrement juste).
+pub fn question_score(quiz: &Quiz, answer: Option<&UserAnswer>, scoring: &Scoring) -> f32 {
+    let chosen = match answer {
+        Some(UserAnswer::Choices(chosen)) => chosen,
+        Some(UserAnswer::Text(text)) => {
+            let accepted = match (quiz.kind(), &quiz.numeric) {
+                (QuestionKind::Text, _) => is_accepted(text, &quiz.accepted_answers, &quiz.matching),
+                (QuestionKind::Numeric, Some(expected)) => numeric::is_correct(text, expected),
+                _ => false,
+            };
+            return if accepted { 1.0 } else { 0.0 };
+        }
+        Some(UserAnswer::Order(order)) => {
+            if quiz.kind() != QuestionKind::Ordering {
+                return 0.0;
+            }
+            let credit = quiz.ordering_credit.unwrap_or(scoring.ordering_credit);
+            return ordering_score(order, &quiz.correct_answer.letters(), credit);
+        }
+        Some(UserAnswer::Pairs(pairs)) => {
+            // Chaque association juste rapporte sa part des points
+            if quiz.kind() != QuestionKind::Matching || quiz.pairs.is_empty() {
+                return 0.0;
+            }
+            let right = quiz
+                .pairs
+                .iter()
+                .filter(|(left, expected)| pairs.get(*left) == Some(*expected))
+                .count();
+            return right as f32 / quiz.pairs.len() as f32;
+        }
+        Some(UserAnswer::Point(point)) => {
+            let hit = quiz.kind() == QuestionKind::Hotspot
+                && quiz
+                    .hotspot
+                    .as_ref()
+                    .is_some_and(|hotspot| hotspot.regions.iter().any(|region| region.contains(*point)));
+            return if hit { 1.0 } else { 0.0 };
+        }
+        None => return 0.0,
+    };
+    // Rien de coché équivaut à ne pas répondre, quel que soit le barème
+    if chosen.is_empty() {
+        return 0.0;
+    }
+    let correct = quiz.correct_answer.letters();
+
+    match quiz.kind() {
+        QuestionKind::Single => {
+            if chosen.len() == 1 && correct.contains(&chosen[0].as_str()) {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        QuestionKind::Multiple => {
+            let is_chosen = |letter: &str| chosen.iter().any(|c| c == letter);
+            let right = correct.iter().filter(|letter| is_chosen(letter)).count();
+            let wrong = chosen.iter().filter(|c| !correct.contains(&c.as_str())).count();
+
+            match quiz.partial_credit.unwrap_or(scoring.partial_credit) {
+                PartialCredit::AllOrNothing => {
+                    if right == correct.len() && wrong == 0 { 1.0 } else { 0.0 }
+                }
+                PartialCredit::Proportional => {
+                    // Chaque réponse proposée compte : cochée si elle est bonne, vide sinon
+                    let total = quiz.answers.len();
+                    if total == 0 {
+                        return 0.0;
+                    }
+                    let matching = quiz
+                        .answers
+                        .iter()
+                        .filter(|a| is_chosen(&a.letter) == correct.contains(&a.letter.as_str()))
+                        .count();
+                    matching as f32 / total as f32
+                }
+                PartialCredit::RightMinusWrong => {
+                    if correct.is_empty() {
+                        return 0.0;
+                    }
+                    ((right as f32 - wrong as f32) / correct.len() as f32).max(0.0)
+                }
+            }
+        }
+        QuestionKind::Text
+        | QuestionKind::Numeric
+        | QuestionKind::Ordering
+        | QuestionKind::Matching
+        | QuestionKind::Hotspot => 0.0,
+    }
+}
+
+/// Points obtenus à une question : sa note multipliée par son poids, ou la pénalité en négatif
+/// si la réponse donnée est entièrement fausse. Une question sans réponse rapporte 0.
+pub fn question_points(quiz: &Quiz, answer: Option<&UserAnswer>, scoring: &Scoring) -> f32 {
+    let score = question_score(quiz, answer, scoring);
+    if score > 0.0 {
+        score * quiz.points()
+    } else if answer.is_some_and(|a| !a.is_empty()) {
+        0.0 - quiz.penalty.unwrap_or(scoring.penalty) // 0.0 - 0.0 évite d'afficher « -0 »
+    } else {
+        0.0
+    }
+}
+
+/// Bilan d'une session de quiz.
+pub struct SessionScore {
+    pub points: f32,
+    pub max_points: f32,
+    /// Verdict par rapport au seuil de réussite, `None` si le quiz n'en définit pas
+    pub passed: Option<bool>,
+}
+
+impl SessionScore {
+    pub fn percent(&self) -> f32 {
+        if self.max_points > 0.0 { self.points / self.max_points * 100.0 } else { 0.0 }
+    }
+}
+
+pub fn session_score(questions: &[Quiz], answers: &[Option<UserAnswer>], scoring: &Scoring) -> SessionScore {
+    let points: f32 = questions
+        .iter()
+        .enumerate()
+        .map(|(i, quiz)| question_points(quiz, answers.get(i).and_then(|a| a.as_ref()), scoring))
+        .sum();
+    let mut score = SessionScore {
+        // Les pénalités ne font jamais descendre le total sous zéro
+        points: points.max(0.0),
+        max_points: questions.iter().map(Quiz::points).sum(),
+        passed: None,
+    };
+    score.passed = scoring.pass_mark.map(|mark| score.percent() >= mark);
+    score
+}
+
+fn ordering_score(order: &[String], expected: &[&str], credit: OrderingCredit) -> f32 {
+    let n = expected.len();
+    if n == 0 {
+        return 0.0;
+    }
+    if order.len() == n && order.iter().zip(expected).all(|(a, b)| a == b) {
+        return 1.0;
+    }
+    // Rang attendu de chaque élément, dans l'ordre choisi par l'utilisateur
+    let ranks: Vec<usize> = order
+        .iter()
+        .filter_map(|letter| expected.iter().position(|e| e == letter))
+        .collect();
+
+    match credit {
+        OrderingCredit::Exact => 0.0,
+        OrderingCredit::LongestSubsequence => {
+            // Plus longue sous-suite croissante des rangs (O(n²), les listes sont courtes)
+            let mut best = vec![1; ranks.len()];
+            for i in 0..ranks.len() {
+                for j in 0..i {
+                    if ranks[j] < ranks[i] {
+                        best[i] = best[i].max(best[j] + 1);
+                    }
+                }
+            }
+            best.into_iter().max().unwrap_or(0) as f32 / n as f32
+        }
+        OrderingCredit::Kendall => {
+            if n < 2 || ranks.len() != n {
+                return 0.0;
+            }
+            let mut inversions = 0;
+            for i in 0..n {
+                for j in i + 1..n {
+                    if ranks[i] > ranks[j] {
+                        inversions += 1;
+                    }
+                }
+            }
+            1.0 - inversions as f32 / (n * (n - 1) / 2) as f32
+        }
+    }
+}
+
+/// Affiche un nombre de points sans décimales inutiles (« 3 », « 2.5 »).
+pub fn format_points(points: f32) -> String {
+    if (points - points.round()).abs() < 0.005 {
+        format!("{}", points.round())
+    } else {
+        format!("{:.2}", points).trim_end_matches('0').to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quiz(yaml: &str) -> Quiz {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn choices(letters: &[&str]) -> UserAnswer {
+        UserAnswer::Choices(letters.iter().map(|l| l.to_string()).collect())
+    }
+
+    /// Quatre réponses, dont A et C sont bonnes.
+    fn multiple(credit: PartialCredit) -> (Quiz, Scoring) {
+        let quiz = quiz(
+            "question: Lesquels ?
+answers: [{letter: A, text: a}, {letter: B, text: b}, {letter: C, text: c}, {letter: D, text: d}]
+correct_answer: [A, C]",
+        );
+        (quiz, Scoring { partial_credit: credit, ..Scoring::default() })
+    }
+
+    #[test]
+    fn all_or_nothing_requires_exact_selection() {
+        let (quiz, scoring) = multiple(PartialCredit::AllOrNothing);
+        assert_eq!(question_score(&quiz, Some(&choices(&["A", "C"])), &scoring), 1.0);
+        assert_eq!(question_score(&quiz, Some(&choices(&["A"])), &scoring), 0.0);
+        assert_eq!(question_score(&quiz, Some(&choices(&["A", "B", "C"])), &scoring), 0.0);
+    }
+
+    #[test]
+    fn proportional_counts_every_answer() {
+        let (quiz, scoring) = multiple(PartialCredit::Proportional);
+        // A juste, B et D laissées vides à raison, C oubliée
+        assert_eq!(question_score(&quiz, Some(&choices(&["A"])), &scoring), 0.75);
+        assert_eq!(question_score(&quiz, Some(&choices(&["B", "D"])), &scoring), 0.0);
+        // Ne rien cocher ne rapporte rien, même si la moitié des cases sont justement vides
+        assert_eq!(question_score(&quiz, Some(&choices(&[])), &scoring), 0.0);
+    }
+
+    #[test]
+    fn right_minus_wrong_never_goes_below_zero() {
+        let (quiz, scoring) = multiple(PartialCredit::RightMinusWrong);
+        assert_eq!(question_score(&quiz, Some(&choices(&["A", "C"])), &scoring), 1.0);
+        assert_eq!(question_score(&quiz, Some(&choices(&["A", "B"])), &scoring), 0.0);
+        assert_eq!(question_score(&quiz, Some(&choices(&["A", "C", "D"])), &scoring), 0.5);
+        assert_eq!(question_score(&quiz, Some(&choices(&["B", "D"])), &scoring), 0.0);
+    }
+
+    #[test]
+    fn question_credit_overrides_quiz_credit() {
+        let (mut quiz, scoring) = multiple(PartialCredit::AllOrNothing);
+        quiz.partial_credit = Some(PartialCredit::RightMinusWrong);
+        assert_eq!(question_score(&quiz, Some(&choices(&["A"])), &scoring), 0.5);
+    }
+
+    #[test]
+    fn single_choice_needs_the_right_letter() {
+        let quiz = quiz("question: Capitale ?\nanswers: [{letter: A, text: Londres}, {letter: B, text: Paris}]\ncorrect_answer: B");
+        let scoring = Scoring::default();
+        assert_eq!(question_score(&quiz, Some(&choices(&["B"])), &scoring), 1.0);
+        assert_eq!(question_score(&quiz, Some(&choices(&["A"])), &scoring), 0.0);
+        assert_eq!(question_score(&quiz, None, &scoring), 0.0);
+    }
+
+    fn order(letters: &[&str]) -> Vec<String> {
+        letters.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn exact_ordering_is_all_or_nothing() {
+        let expected = ["A", "B", "C", "D"];
+        assert_eq!(ordering_score(&order(&["A", "B", "C", "D"]), &expected, OrderingCredit::Exact), 1.0);
+        assert_eq!(ordering_score(&order(&["B", "A", "C", "D"]), &expected, OrderingCredit::Exact), 0.0);
+    }
+
+    #[test]
+    fn longest_subsequence_counts_items_already_in_order() {
+        let expected = ["A", "B", "C", "D"];
+        let credit = OrderingCredit::LongestSubsequence;
+        assert_eq!(ordering_score(&order(&["B", "A", "C", "D"]), &expected, credit), 0.75);
+        assert_eq!(ordering_score(&order(&["D", "C", "B", "A"]), &expected, credit), 0.25);
+        assert_eq!(ordering_score(&order(&["C", "D", "A", "B"]), &expected, credit), 0.5);
+    }
+
+    #[test]
+    fn kendall_counts_inverted_pairs() {
+        let expected = ["A", "B", "C", "D"];
+        let credit = OrderingCredit::Kendall;
+        // Une paire inversée sur six
+        let score = ordering_score(&order(&["B", "A", "C", "D"]), &expected, credit);
+        assert!((score - 5.0 / 6.0).abs() < 1e-6);
+        assert_eq!(ordering_score(&order(&["D", "C", "B", "A"]), &expected, credit), 0.0);
+        // Une liste incomplète ne se compare pas paire à paire
+        assert_eq!(ordering_score(&order(&["A", "B", "C"]), &expected, credit), 0.0);
+    }
+
+    #[test]
+    fn ordering_question_uses_its_own_credit() {
+        let quiz = quiz(
+            "question: Classez
+kind: ordering
+answers: [{letter: A, text: a}, {letter: B, text: b}, {letter: C, text: c}]
+correct_answer: [A, B, C]
+ordering_credit: longest_subsequence",
+        );
+        let answer = UserAnswer::Order(order(&["A", "C", "B"]));
+        let score = question_score(&quiz, Some(&answer), &Scoring::default());
+        assert!((score - 2.0 / 3.0).abs() < 1e-6);
+        assert_eq!(question_score(&quiz, None, &Scoring::default()), 0.0);
+    }
+
+    #[test]
+    fn each_right_pair_earns_its_share() {
+        let quiz = quiz(
+            "question: Associez
+answers: [{letter: A, text: France}, {letter: B, text: Italie}, {letter: C, text: Espagne}]
+matches: [{letter: '1', text: Rome}, {letter: '2', text: Paris}, {letter: '3', text: Madrid}]
+pairs: {A: '2', B: '1', C: '3'}",
+        );
+        let pairs = |pairs: &[(&str, &str)]| {
+            UserAnswer::Pairs(pairs.iter().map(|(l, r)| (l.to_string(), r.to_string())).collect())
+        };
+        let scoring = Scoring::default();
+        assert_eq!(question_score(&quiz, Some(&pairs(&[("A", "2"), ("B", "1"), ("C", "3")])), &scoring), 1.0);
+        let score = question_score(&quiz, Some(&pairs(&[("A", "2"), ("B", "3"), ("C", "1")])), &scoring);
+        assert!((score - 1.0 / 3.0).abs() < 1e-6);
+        // Une association oubliée ne rapporte rien, sans pénaliser les autres
+        let score = question_score(&quiz, Some(&pairs(&[("A", "2"), ("B", "1")])), &scoring);
+        assert!((score - 2.0 / 3.0).abs() < 1e-6);
+        assert_eq!(question_score(&quiz, Some(&pairs(&[])), &scoring), 0.0);
+    }
+
+    #[test]
+    fn wrong_answers_are_penalised_but_skipped_ones_are_not() {
+        let quiz = quiz("question: Combien ?\nnumeric: {value: 42}\npoints: 2");
+        let scoring = Scoring { penalty: 0.5, ..Scoring::default() };
+        let right = UserAnswer::Text("42".to_string());
+        let wrong = UserAnswer::Text("41".to_string());
+        assert_eq!(question_points(&quiz, Some(&right), &scoring), 2.0);
+        assert_eq!(question_points(&quiz, Some(&wrong), &scoring), -0.5);
+        assert_eq!(question_points(&quiz, None, &scoring), 0.0);
+        // Une saisie vide compte comme une absence de réponse
+        assert_eq!(question_points(&quiz, Some(&UserAnswer::Text("  ".to_string())), &scoring), 0.0);
+    }
+
+    #[test]
+    fn question_penalty_overrides_quiz_penalty() {
+        let mut quiz = quiz("question: Combien ?\nnumeric: {value: 42}");
+        quiz.penalty = Some(1.0);
+        let scoring = Scoring { penalty: 0.25, ..Scoring::default() };
+        assert_eq!(question_points(&quiz, Some(&UserAnswer::Text("0".to_string())), &scoring), -1.0);
+    }
+
+    #[test]
+    fn zero_penalty_is_not_negative_zero() {
+        let quiz = quiz("question: Combien ?\nnumeric: {value: 42}");
+        let points = question_points(&quiz, Some(&UserAnswer::Text("0".to_string())), &Scoring::default());
+        assert!(points.is_sign_positive());
+        assert_eq!(format_points(points), "0");
+    }
+
+    #[test]
+    fn session_total_is_floored_at_zero() {
+        let questions = vec![
+            quiz("question: Un ?\nnumeric: {value: 1}"),
+            quiz("question: Deux ?\nnumeric: {value: 2}"),
+        ];
+        let answers = vec![Some(UserAnswer::Text("9".to_string())), Some(UserAnswer::Text("9".to_string()))];
+        let scoring = Scoring { penalty: 1.0, pass_mark: Some(50.0), ..Scoring::default() };
+        let score = session_score(&questions, &answers, &scoring);
+        assert_eq!(score.points, 0.0);
+        assert_eq!(score.max_points, 2.0);
+        assert_eq!(score.passed, Some(false));
+    }
+
+    #[test]
+    fn pass_mark_is_inclusive() {
+        let questions = vec![
+            quiz("question: Un ?\nnumeric: {value: 1}"),
+            quiz("question: Deux ?\nnumeric: {value: 2}"),
+        ];
+        let answers = vec![Some(UserAnswer::Text("1".to_string())), None];
+        let scoring = Scoring { pass_mark: Some(50.0), ..Scoring::default() };
+        let score = session_score(&questions, &answers, &scoring);
+        assert_eq!(score.percent(), 50.0);
+        assert_eq!(score.passed, Some(true));
+        assert_eq!(session_score(&questions, &answers, &Scoring::default()).passed, None);
+    }
+
+    #[test]
+    fn points_are_formatted_without_useless_decimals() {
+        assert_eq!(format_points(3.0), "3");
+        assert_eq!(format_points(2.5), "2.5");
+        assert_eq!(format_points(0.25), "0.25");
+    }
+}
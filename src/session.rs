@@ -0,0 +1,239 @@
+use crate::models::{Answer, Quiz, ShuffleMode, UserAnswer};
+use crate::shuffle::seed_from_str;
+use crate::storage::{data_dir, data_file};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Session en cours enregistrée sur disque pour pouvoir la reprendre après fermeture de l'application.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SavedSession {
+    /// `metadata.id` du quiz, ou son chemin relatif à la bibliothèque : la session survit au renommage d'un quiz identifié
+    pub quiz_key: String,
+    /// Empreinte du contenu du fichier au moment de l'enregistrement
+    pub content_hash: u64,
+    pub shuffle: ShuffleMode,
+    pub shuffle_seed: u64,
+    pub current_question: usize,
+    /// Temps écoulé depuis le début du quiz, en secondes
+    #[serde(default)]
+    pub elapsed: f64,
+    /// Questions dans l'ordre de la session
+    pub questions: Vec<SavedQuestion>,
+}
+
+/// État d'une question dans une session enregistrée.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SavedQuestion {
+    pub id: Option<String>,
+    /// Empreinte de ce dont dépend la réponse (voir `question_layout`)
+    pub layout: u64,
+    pub answer: Option<UserAnswer>,
+    pub checked: bool,
+    /// Temps passé, en secondes
+    pub seconds: f64,
+}
+
+impl SavedSession {
+    /// Question enregistrée correspondant à chaque question actuelle (dans l'ordre de la session),
+    /// ou la raison pour laquelle les réponses ne s'appliquent plus au quiz tel qu'il est maintenant.
+    /// Les questions sont retrouvées par leur `id` quand toutes en ont un, par leur position sinon.
+    pub fn match_questions(&self, content_hash: u64, questions: &[Quiz]) -> Result<Vec<Option<usize>>, String> {
+        let same_count = self.questions.len() == questions.len();
+        if self.content_hash == content_hash && same_count {
+            return Ok((0..questions.len()).map(Some).collect());
+        }
+        if !self.by_id(questions) && !same_count {
+            return Err(format!(
+                "le quiz compte maintenant {} questions au lieu de {}",
+                questions.len(),
+                self.questions.len()
+            ));
+        }
+
+        let mapping = self.candidates(questions);
+        for (quiz, saved) in questions.iter().zip(&mapping) {
+            if let Some(j) = saved
+                && self.questions[*j].layout != question_layout(quiz)
+            {
+                return Err(format!("la question « {} » a changé depuis", quiz.question));
+            }
+        }
+        let lost = self
+            .questions
+            .iter()
+            .enumerate()
+            .any(|(j, saved)| saved.answer.is_some() && !mapping.contains(&Some(j)));
+        if lost {
+            return Err("des questions déjà répondues ont été supprimées".to_string());
+        }
+        Ok(mapping)
+    }
+
+    /// Variante tolérante de `match_questions`, pour un quiz modifié en cours de session : seules
+    /// les questions retrouvées et dont la forme n'a pas changé gardent leur réponse.
+    pub fn matching_questions(&self, questions: &[Quiz]) -> Vec<Option<usize>> {
+        self.candidates(questions)
+            .into_iter()
+            .zip(questions)
+            .map(|(saved, quiz)| saved.filter(|j| self.questions[*j].layout == question_layout(quiz)))
+            .collect()
+    }
+
+    fn by_id(&self, questions: &[Quiz]) -> bool {
+        questions.iter().all(|quiz| quiz.id.is_some()) && self.questions.iter().all(|q| q.id.is_some())
+    }
+
+    /// Question enregistrée de même `id`, ou de même position si le nombre de questions est inchangé.
+    fn candidates(&self, questions: &[Quiz]) -> Vec<Option<usize>> {
+        if self.by_id(questions) {
+            questions
+                .iter()
+                .map(|quiz| self.questions.iter().position(|saved| saved.id == quiz.id))
+                .collect()
+        } else if self.questions.len() == questions.len() {
+            (0..questions.len()).map(Some).collect()
+        } else {
+            vec![None; questions.len()]
+        }
+    }
+}
+
+/// Empreinte du contenu d'un fichier de quiz.
+pub fn content_hash(quiz_path: &str) -> u64 {
+    fs::read_to_string(quiz_path).map_or(0, |content| seed_from_str(&content))
+}
+
+/// Empreinte de ce qui donne un sens à une réponse enregistrée : type de la question et lettres
+/// proposées, quel que soit leur ordre. Corriger une faute dans un énoncé ne la modifie pas.
+pub fn question_layout(quiz: &Quiz) -> u64 {
+    let letters = |answers: &[Answer]| {
+        let mut letters: Vec<&str> = answers.iter().map(|a| a.letter.as_str()).collect();
+        letters.sort_unstable();
+        letters.join(",")
+    };
+    seed_from_str(&format!("{:?}|{}|{}", quiz.kind(), letters(&quiz.answers), letters(&quiz.matches)))
+}
+
+/// Un fichier par quiz dans `sessions/`, nommé d'après l'empreinte de sa clé.
+fn session_name(quiz_key: &str) -> PathBuf {
+    Path::new("sessions").join(format!("{:016x}.json", seed_from_str(quiz_key)))
+}
+
+fn session_file(quiz_key: &str) -> Option<PathBuf> {
+    Some(data_dir()?.join(session_name(quiz_key)))
+}
+
+pub fn save(session: &SavedSession) -> io::Result<()> {
+    let json = serde_json::to_string(session).map_err(io::Error::other)?;
+    fs::write(data_file(session_name(&session.quiz_key))?, json)
+}
+
+/// Session inachevée enregistrée pour ce quiz, s'il y en a une lisible.
+pub fn load(quiz_key: &str) -> Option<SavedSession> {
+    let content = fs::read_to_string(session_file(quiz_key)?).ok()?;
+    serde_json::from_str::<SavedSession>(&content)
+        .ok()
+        .filter(|session| session.quiz_key == quiz_key)
+}
+
+pub fn exists(quiz_key: &str) -> bool {
+    session_file(quiz_key).is_some_and(|path| path.is_file())
+}
+
+pub fn remove(quiz_key: &str) {
+    if let Some(path) = session_file(quiz_key) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn question(id: Option<&str>, letters: &[&str]) -> Quiz {
+        let answers: Vec<String> = letters.iter().map(|l| format!("{{letter: {}, text: {}}}", l, l)).collect();
+        let id = id.map(|id| format!("id: {}\n", id)).unwrap_or_default();
+        serde_yaml::from_str(&format!("{}question: Q ?\nanswers: [{}]\ncorrect_answer: A", id, answers.join(", ")))
+            .unwrap()
+    }
+
+    /// Session enregistrée sur `questions`, avec une réponse pour chaque question de `answered`.
+    fn session(questions: &[Quiz], answered: &[usize]) -> SavedSession {
+        SavedSession {
+            quiz_key: "quiz.yaml".to_string(),
+            content_hash: 1,
+            shuffle: ShuffleMode::default(),
+            shuffle_seed: 0,
+            current_question: 0,
+            elapsed: 0.0,
+            questions: questions
+                .iter()
+                .enumerate()
+                .map(|(i, quiz)| SavedQuestion {
+                    id: quiz.id.clone(),
+                    layout: question_layout(quiz),
+                    answer: answered.contains(&i).then(|| UserAnswer::Choices(vec!["A".to_string()])),
+                    checked: false,
+                    seconds: 0.0,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn unchanged_file_maps_each_question_to_itself() {
+        let questions = vec![question(None, &["A", "B"]), question(None, &["A", "B", "C"])];
+        let saved = session(&questions, &[0]);
+        assert_eq!(saved.match_questions(1, &questions), Ok(vec![Some(0), Some(1)]));
+    }
+
+    #[test]
+    fn questions_with_ids_are_found_after_reordering() {
+        let q1 = question(Some("q1"), &["A", "B"]);
+        let q2 = question(Some("q2"), &["A", "B"]);
+        let q3 = question(Some("q3"), &["A", "B"]);
+        let saved = session(&[q1.clone(), q2.clone()], &[0, 1]);
+        assert_eq!(saved.match_questions(2, &[q2, q3, q1]), Ok(vec![Some(1), None, Some(0)]));
+    }
+
+    #[test]
+    fn questions_without_ids_are_matched_by_position() {
+        let questions = vec![question(None, &["A", "B"]), question(None, &["A", "B"])];
+        let saved = session(&questions, &[1]);
+        assert_eq!(saved.match_questions(2, &questions), Ok(vec![Some(0), Some(1)]));
+    }
+
+    #[test]
+    fn question_count_change_without_ids_is_refused() {
+        let questions = vec![question(None, &["A", "B"]), question(None, &["A", "B"])];
+        let saved = session(&questions, &[]);
+        let error = saved.match_questions(2, &questions[..1]).unwrap_err();
+        assert!(error.contains("1 questions au lieu de 2"), "{}", error);
+    }
+
+    #[test]
+    fn changed_letters_invalidate_the_answer() {
+        let saved = session(&[question(Some("q1"), &["A", "B"])], &[0]);
+        let changed = [question(Some("q1"), &["A", "B", "C"])];
+        assert!(saved.match_questions(2, &changed).is_err());
+        assert_eq!(saved.matching_questions(&changed), vec![None]);
+
+        // L'ordre des réponses dans le fichier ne compte pas.
+        let reordered = [question(Some("q1"), &["B", "A"])];
+        assert_eq!(saved.match_questions(2, &reordered), Ok(vec![Some(0)]));
+    }
+
+    #[test]
+    fn removing_an_answered_question_is_refused() {
+        let q1 = question(Some("q1"), &["A", "B"]);
+        let q2 = question(Some("q2"), &["A", "B"]);
+        let answered = session(&[q1.clone(), q2.clone()], &[1]);
+        assert!(answered.match_questions(2, std::slice::from_ref(&q1)).is_err());
+        assert_eq!(answered.matching_questions(std::slice::from_ref(&q1)), vec![Some(0)]);
+
+        let unanswered = session(&[q1.clone(), q2], &[0]);
+        assert_eq!(unanswered.match_questions(2, &[q1]), Ok(vec![Some(0)]));
+    }
+}
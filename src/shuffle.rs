@@ -0,0 +1,206 @@
+use crate::models::{QuestionKind, Quiz, ShuffleMode};
+
+/// Générateur pseudo-aléatoire déterministe (SplitMix64) : une même graine redonne toujours le même ordre.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Entier dans `0..n` (n > 0).
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// Permutation de `0..len` (Fisher-Yates) déterminée par `seed`.
+pub fn shuffled_indices(len: usize, seed: u64) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..len).collect();
+    let mut rng = Rng::new(seed);
+    for i in (1..len).rev() {
+        indices.swap(i, rng.below(i + 1));
+    }
+    indices
+}
+
+/// Graine stable dérivée d'un texte (FNV-1a), identique d'une exécution à l'autre.
+pub fn seed_from_str(text: &str) -> u64 {
+    text.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01B3)
+    })
+}
+
+/// Graine tirée de l'horloge, pour une session sans graine imposée.
+pub fn random_seed() -> u64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+    Rng::new(nanos).next_u64()
+}
+
+/// Mélange les réponses d'une question à choix ou de remise en ordre et leur attribue de nouvelles
+/// lettres d'affichage. Les réponses épinglées restent à la fin ; `Answer.letter` n'est pas modifié.
+pub fn shuffle_answers(quiz: &mut Quiz, seed: u64) {
+    if !matches!(quiz.kind(), QuestionKind::Single | QuestionKind::Multiple | QuestionKind::Ordering) {
+        return;
+    }
+    let labels: Vec<String> = quiz.answers.iter().map(|a| a.letter.clone()).collect();
+    let (pinned, free): (Vec<_>, Vec<_>) = std::mem::take(&mut quiz.answers).into_iter().partition(|a| a.pinned);
+
+    // La graine dépend de l'énoncé : l'ordre des réponses ne change pas avec celui des questions
+    let mut seed = seed ^ seed_from_str(&quiz.question);
+    let mut order = shuffled_indices(free.len(), seed);
+    // Une remise en ordre ne doit pas s'afficher déjà rangée : on retire jusqu'à obtenir un autre ordre
+    if quiz.kind() == QuestionKind::Ordering && free.len() > 1 {
+        let expected = quiz.correct_answer.letters();
+        while order.iter().map(|&i| free[i].letter.as_str()).eq(expected.iter().copied()) {
+            seed = Rng::new(seed).next_u64();
+            order = shuffled_indices(free.len(), seed);
+        }
+    }
+    quiz.answers = order.into_iter().map(|i| free[i].clone()).chain(pinned).collect();
+    quiz.display_letters = quiz
+        .answers
+        .iter()
+        .zip(labels)
+        .map(|(answer, label)| (answer.letter.clone(), label))
+        .collect();
+}
+
+/// Applique le mélange demandé à toutes les questions d'une session.
+pub fn shuffle_session(mut questions: Vec<Quiz>, mode: ShuffleMode, seed: u64) -> Vec<Quiz> {
+    if mode.answers() {
+        for quiz in &mut questions {
+            shuffle_answers(quiz, seed);
+        }
+    }
+    if mode.questions() {
+        let order = shuffled_indices(questions.len(), seed);
+        let mut slots: Vec<Option<Quiz>> = questions.into_iter().map(Some).collect();
+        questions = order.into_iter().filter_map(|i| slots[i].take()).collect();
+    }
+    questions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Scoring, UserAnswer};
+    use crate::scoring::question_score;
+
+    fn quiz(yaml: &str) -> Quiz {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn letters(quiz: &Quiz) -> Vec<&str> {
+        quiz.answers.iter().map(|a| a.letter.as_str()).collect()
+    }
+
+    const CHOICES: &str = "question: Lequel ?
+answers:
+  - {letter: A, text: a}
+  - {letter: B, text: b}
+  - {letter: C, text: c}
+  - {letter: D, text: d}
+  - {letter: E, text: e}
+  - {letter: F, text: Aucune de ces réponses, pinned: true}
+correct_answer: B";
+
+    #[test]
+    fn same_seed_gives_same_permutation() {
+        let order = shuffled_indices(10, 42);
+        assert_eq!(order, shuffled_indices(10, 42));
+        assert_ne!(order, shuffled_indices(10, 43));
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn pinned_answers_stay_last() {
+        for seed in 0..20 {
+            let mut shuffled = quiz(CHOICES);
+            shuffle_answers(&mut shuffled, seed);
+            assert_eq!(shuffled.answers.last().unwrap().letter, "F");
+            assert_eq!(shuffled.display_letter("F"), "F");
+        }
+    }
+
+    #[test]
+    fn answers_are_relabelled_but_scored_on_their_own_letter() {
+        let mut shuffled = quiz(CHOICES);
+        shuffle_answers(&mut shuffled, 7);
+        assert_ne!(letters(&shuffled), ["A", "B", "C", "D", "E", "F"]);
+        // Les lettres affichées suivent l'ordre de présentation
+        let shown: Vec<&str> = shuffled.answers.iter().map(|a| shuffled.display_letter(&a.letter)).collect();
+        assert_eq!(shown, ["A", "B", "C", "D", "E", "F"]);
+        let answer = UserAnswer::Choices(vec!["B".to_string()]);
+        assert_eq!(question_score(&shuffled, Some(&answer), &Scoring::default()), 1.0);
+    }
+
+    #[test]
+    fn answer_order_does_not_depend_on_question_order() {
+        let mut alone = quiz(CHOICES);
+        shuffle_answers(&mut alone, 5);
+        let other = quiz("question: Autre ?\nanswers: [{letter: A, text: a}, {letter: B, text: b}]\ncorrect_answer: A");
+        let session = shuffle_session(vec![other, quiz(CHOICES)], ShuffleMode::Both, 5);
+        let shuffled = session.iter().find(|q| q.question == "Lequel ?").unwrap();
+        assert_eq!(letters(shuffled), letters(&alone));
+    }
+
+    #[test]
+    fn ordering_is_never_shown_already_sorted() {
+        for yaml in [
+            "question: Classez\nkind: ordering\nanswers: [{letter: A, text: a}, {letter: B, text: b}]\ncorrect_answer: [A, B]",
+            "question: Classez\nkind: ordering\nanswers: [{letter: A, text: a}, {letter: B, text: b}, {letter: C, text: c}]\ncorrect_answer: [C, A, B]",
+        ] {
+            let ordering = quiz(yaml);
+            let expected = ordering.correct_answer.letters();
+            for seed in 0..50 {
+                let mut shuffled = ordering.clone();
+                shuffle_answers(&mut shuffled, seed);
+                assert_ne!(letters(&shuffled), expected, "graine {}", seed);
+            }
+        }
+    }
+
+    #[test]
+    fn text_and_matching_questions_are_left_untouched() {
+        let text = quiz("question: Capitale ?\naccepted_answers: [Paris]");
+        let matching = quiz(
+            "question: Associez
+answers: [{letter: A, text: a}, {letter: B, text: b}, {letter: C, text: c}]
+matches: [{letter: '1', text: x}, {letter: '2', text: y}, {letter: '3', text: z}]
+pairs: {A: '1', B: '2', C: '3'}",
+        );
+        for seed in 0..10 {
+            let mut shuffled = matching.clone();
+            shuffle_answers(&mut shuffled, seed);
+            assert_eq!(letters(&shuffled), ["A", "B", "C"]);
+            assert!(shuffled.display_letters.is_empty());
+            let mut shuffled = text.clone();
+            shuffle_answers(&mut shuffled, seed);
+            assert!(shuffled.display_letters.is_empty());
+        }
+    }
+
+    #[test]
+    fn session_shuffle_follows_the_mode() {
+        let questions: Vec<Quiz> = (1..=8).map(|i| quiz(&format!("question: Q{} ?\naccepted_answers: [x]", i))).collect();
+        let texts = |questions: &[Quiz]| questions.iter().map(|q| q.question.clone()).collect::<Vec<_>>();
+        let original = texts(&questions);
+        assert_eq!(texts(&shuffle_session(questions.clone(), ShuffleMode::Answers, 3)), original);
+        let shuffled = texts(&shuffle_session(questions.clone(), ShuffleMode::Questions, 3));
+        assert_ne!(shuffled, original);
+        assert_eq!(shuffled, texts(&shuffle_session(questions, ShuffleMode::Questions, 3)));
+    }
+}
@@ -0,0 +1,20 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Dossier des données locales de l'application (sessions, historique, calendrier de révision).
+pub fn data_dir() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join("quiz-gui"))
+}
+
+/// Chemin de `name` dans le dossier de données, prêt à être écrit : ses dossiers parents sont créés.
+pub fn data_file(name: impl AsRef<Path>) -> io::Result<PathBuf> {
+    let Some(dir) = data_dir() else {
+        return Err(io::Error::new(io::ErrorKind::NotFound, "dossier de données introuvable"));
+    };
+    let path = dir.join(name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    Ok(path)
+}
@@ -0,0 +1,163 @@
+use crate::models::Quiz;
+use crate::shuffle::seed_from_str;
+use crate::storage::{data_dir, data_file};
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+
+/// Nombre maximal de questions jamais révisées ajoutées à une session de révision.
+pub const NEW_CARDS_PER_SESSION: usize = 20;
+
+/// Suivi d'une question en répétition espacée (algorithme SM-2).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Card {
+    /// Révisions réussies d'affilée
+    pub repetitions: u32,
+    /// Intervalle avant la prochaine révision, en jours
+    pub interval: u32,
+    /// Facilité : plus elle est grande, plus les intervalles s'allongent vite
+    pub ease: f32,
+    pub due: NaiveDate,
+}
+
+/// Confiance déclarée après une bonne réponse, qui module l'intervalle suivant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Confidence {
+    Hard,
+    Good,
+    Easy,
+}
+
+impl Confidence {
+    pub const ALL: [Confidence; 3] = [Confidence::Hard, Confidence::Good, Confidence::Easy];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Confidence::Hard => "Difficile",
+            Confidence::Good => "Bien",
+            Confidence::Easy => "Facile",
+        }
+    }
+}
+
+/// Calendrier de révision de toutes les questions, indexé par `card_key`.
+pub type Schedule = BTreeMap<String, Card>;
+
+/// Identité stable d'une question : son quiz (`metadata.id` ou chemin relatif) et son identifiant,
+/// ou à défaut son énoncé, indépendamment de sa position.
+pub fn card_key(quiz_key: &str, quiz: &Quiz) -> String {
+    match &quiz.id {
+        Some(id) => format!("{}#{}", quiz_key, id),
+        None => format!("{}#{:016x}", quiz_key, seed_from_str(&quiz.question)),
+    }
+}
+
+/// Note SM-2 (0 à 5) d'une révision : une réponse fausse ou partielle est un échec,
+/// une réponse juste vaut 3 à 5 selon la confiance (4 si elle n'est pas indiquée).
+pub fn quality(score: f32, confidence: Option<Confidence>) -> u8 {
+    if score <= 0.0 {
+        1
+    } else if score < 1.0 {
+        2
+    } else {
+        match confidence {
+            Some(Confidence::Hard) => 3,
+            Some(Confidence::Good) | None => 4,
+            Some(Confidence::Easy) => 5,
+        }
+    }
+}
+
+/// Nouvel état d'une carte après une révision notée `quality`.
+pub fn review(card: Option<&Card>, quality: u8, today: NaiveDate) -> Card {
+    let mut card = card.cloned().unwrap_or(Card { repetitions: 0, interval: 0, ease: 2.5, due: today });
+    if quality < 3 {
+        // Échec : la question revient dès le lendemain
+        card.repetitions = 0;
+        card.interval = 1;
+    } else {
+        card.interval = match card.repetitions {
+            0 => 1,
+            1 => 6,
+            _ => (card.interval as f32 * card.ease).round() as u32,
+        };
+        card.repetitions += 1;
+    }
+    let miss = f32::from(5 - quality.min(5));
+    card.ease = (card.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(1.3);
+    card.due = today.checked_add_days(Days::new(u64::from(card.interval))).unwrap_or(today);
+    card
+}
+
+const SCHEDULE_FILE: &str = "schedule.json";
+
+/// Calendrier enregistré, vide s'il n'existe pas encore ou est illisible.
+pub fn load() -> Schedule {
+    data_dir()
+        .and_then(|dir| fs::read_to_string(dir.join(SCHEDULE_FILE)).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(schedule: &Schedule) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(schedule).map_err(io::Error::other)?;
+    fs::write(data_file(SCHEDULE_FILE)?, json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, d).unwrap()
+    }
+
+    #[test]
+    fn intervals_grow_with_successful_reviews() {
+        let first = review(None, 4, day(1));
+        assert_eq!((first.repetitions, first.interval, first.due), (1, 1, day(2)));
+        assert_eq!(first.ease, 2.5);
+
+        let second = review(Some(&first), 4, day(2));
+        assert_eq!((second.repetitions, second.interval, second.due), (2, 6, day(8)));
+
+        let third = review(Some(&second), 4, day(8));
+        assert_eq!((third.repetitions, third.interval, third.due), (3, 15, day(23)));
+    }
+
+    #[test]
+    fn failure_brings_the_card_back_tomorrow() {
+        let learnt = Card { repetitions: 4, interval: 30, ease: 2.5, due: day(1) };
+        let failed = review(Some(&learnt), 2, day(1));
+        assert_eq!((failed.repetitions, failed.interval, failed.due), (0, 1, day(2)));
+        assert!(failed.ease < learnt.ease);
+
+        let relearnt = review(Some(&failed), 4, day(2));
+        assert_eq!(relearnt.interval, 1);
+    }
+
+    #[test]
+    fn ease_follows_quality_and_never_drops_below_floor() {
+        let card = Card { repetitions: 2, interval: 6, ease: 2.5, due: day(1) };
+        assert!((review(Some(&card), 5, day(1)).ease - 2.6).abs() < 1e-5);
+        assert!((review(Some(&card), 3, day(1)).ease - 2.36).abs() < 1e-5);
+
+        let mut card = review(None, 0, day(1));
+        for _ in 0..10 {
+            card = review(Some(&card), 0, day(1));
+        }
+        assert_eq!(card.ease, 1.3);
+    }
+
+    #[test]
+    fn quality_depends_on_score_and_confidence() {
+        assert_eq!(quality(0.0, Some(Confidence::Easy)), 1);
+        assert_eq!(quality(0.5, None), 2);
+        assert_eq!(quality(1.0, Some(Confidence::Hard)), 3);
+        assert_eq!(quality(1.0, None), 4);
+        assert_eq!(quality(1.0, Some(Confidence::Good)), 4);
+        assert_eq!(quality(1.0, Some(Confidence::Easy)), 5);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::models::TextMatching;
+use regex::Regex;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Indique si `input` correspond à l'une des réponses acceptées.
+pub fn is_accepted(input: &str, accepted: &[String], matching: &TextMatching) -> bool {
+    let input = normalize(input, matching);
+    if input.is_empty() {
+        return false;
+    }
+
+    accepted.iter().any(|candidate| {
+        if matching.regex {
+            compile_pattern(candidate, matching).is_ok_and(|re| re.is_match(&input))
+        } else {
+            let candidate = normalize(candidate, matching);
+            if matching.max_distance == 0 {
+                input == candidate
+            } else {
+                levenshtein(&input, &candidate) <= matching.max_distance
+            }
+        }
+    })
+}
+
+/// Compile une réponse acceptée en expression régulière ancrée sur toute la saisie.
+pub fn compile_pattern(pattern: &str, matching: &TextMatching) -> Result<Regex, regex::Error> {
+    let pattern = if matching.ignore_accents { strip_accents(pattern) } else { pattern.to_string() };
+    let flags = if matching.case_insensitive { "(?i)" } else { "" };
+    Regex::new(&format!("{}^(?:{})$", flags, pattern))
+}
+
+fn normalize(text: &str, matching: &TextMatching) -> String {
+    let mut text = if matching.trim {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    } else {
+        text.to_string()
+    };
+    if matching.ignore_accents {
+        text = strip_accents(&text);
+    }
+    // Avec une regex, la casse est gérée par le flag (?i)
+    if matching.case_insensitive && !matching.regex {
+        text = text.to_lowercase();
+    }
+    text
+}
+
+/// Forme d'un texte pour la recherche : minuscules, sans accents ni espaces superflus.
+pub fn search_key(text: &str) -> String {
+    strip_accents(&text.split_whitespace().collect::<Vec<_>>().join(" ")).to_lowercase()
+}
+
+fn strip_accents(text: &str) -> String {
+    text.nfd().filter(|c| !is_combining_mark(*c)).nfc().collect()
+}
+
+/// Distance d'édition (insertions, suppressions, substitutions) entre deux chaînes.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accepted(answers: &[&str]) -> Vec<String> {
+        answers.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn default_matching_ignores_case_and_spaces() {
+        let matching = TextMatching::default();
+        assert!(is_accepted("  PARIS ", &accepted(&["Paris"]), &matching));
+        assert!(is_accepted("la  tour eiffel", &accepted(&["La Tour Eiffel"]), &matching));
+        assert!(!is_accepted("Lyon", &accepted(&["Paris"]), &matching));
+        assert!(!is_accepted("   ", &accepted(&[""]), &matching));
+    }
+
+    #[test]
+    fn accents_are_folded_only_when_asked() {
+        let strict = TextMatching::default();
+        let folded = TextMatching { ignore_accents: true, ..TextMatching::default() };
+        assert!(!is_accepted("elephant", &accepted(&["Éléphant"]), &strict));
+        assert!(is_accepted("elephant", &accepted(&["Éléphant"]), &folded));
+        assert!(is_accepted("ÉLÉPHANT", &accepted(&["elephant"]), &folded));
+    }
+
+    #[test]
+    fn case_is_kept_when_asked() {
+        let matching = TextMatching { case_insensitive: false, ..TextMatching::default() };
+        assert!(!is_accepted("paris", &accepted(&["Paris"]), &matching));
+        assert!(is_accepted("Paris", &accepted(&["Paris"]), &matching));
+    }
+
+    #[test]
+    fn fuzzy_matching_allows_a_few_typos() {
+        let matching = TextMatching { max_distance: 1, ..TextMatching::default() };
+        assert!(is_accepted("Pariss", &accepted(&["Paris"]), &matching));
+        assert!(is_accepted("Pris", &accepted(&["Paris"]), &matching));
+        assert!(!is_accepted("Prs", &accepted(&["Paris"]), &matching));
+    }
+
+    #[test]
+    fn levenshtein_counts_edits() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("abc", ""), 3);
+        assert_eq!(levenshtein("chat", "chats"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        // Caractères, pas octets
+        assert_eq!(levenshtein("été", "ete"), 2);
+    }
+
+    #[test]
+    fn regexes_are_anchored_on_the_whole_input() {
+        let matching = TextMatching { regex: true, ..TextMatching::default() };
+        let patterns = accepted(&["(le )?rh[oô]ne"]);
+        assert!(is_accepted("Le Rhône", &patterns, &matching));
+        assert!(is_accepted("rhone", &patterns, &matching));
+        assert!(!is_accepted("le rhône et la saône", &patterns, &matching));
+        // L'alternative ne s'échappe pas des ancres
+        assert!(!is_accepted("xb", &accepted(&["a|b"]), &matching));
+    }
+
+    #[test]
+    fn regexes_can_ignore_accents() {
+        let matching = TextMatching { regex: true, ignore_accents: true, ..TextMatching::default() };
+        assert!(is_accepted("Rhône", &accepted(&["rhone"]), &matching));
+        assert!(compile_pattern("(", &matching).is_err());
+    }
+
+    #[test]
+    fn search_key_folds_case_accents_and_spaces() {
+        assert_eq!(search_key("  Géographie   de l'Europe "), "geographie de l'europe");
+    }
+}
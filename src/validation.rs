@@ -0,0 +1,432 @@
+use crate::models::{QuestionKind, Quiz, QuizFile, Region};
+use crate::text_match::compile_pattern;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Le quiz ne peut pas être passé correctement.
+    Error,
+    /// Le quiz fonctionne mais quelque chose semble suspect.
+    Warning,
+}
+
+/// Problème détecté lors de la validation d'un fichier de quiz.
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub severity: Severity,
+    /// Index (0-based) de la question concernée, `None` si le problème touche tout le fichier
+    pub question: Option<usize>,
+    pub message: String,
+}
+
+impl Issue {
+    fn error(question: Option<usize>, message: String) -> Self {
+        Self { severity: Severity::Error, question, message }
+    }
+
+    fn warning(question: Option<usize>, message: String) -> Self {
+        Self { severity: Severity::Warning, question, message }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.question {
+            Some(i) => write!(f, "Q{} : {}", i + 1, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+pub fn has_errors(issues: &[Issue]) -> bool {
+    issues.iter().any(|issue| issue.severity == Severity::Error)
+}
+
+/// Vérifie la cohérence d'un quiz déjà désérialisé et retourne tous les problèmes trouvés.
+pub fn validate_quiz_file(quiz_file: &QuizFile) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    if quiz_file.questions.is_empty() {
+        issues.push(Issue::error(None, "le fichier ne contient aucune question".to_string()));
+    }
+    if quiz_file.scoring.penalty < 0.0 {
+        issues.push(Issue::error(None, "la pénalité (penalty) ne peut pas être négative".to_string()));
+    }
+    if let Some(mark) = quiz_file.scoring.pass_mark
+        && !(0.0..=100.0).contains(&mark)
+    {
+        issues.push(Issue::error(None, format!("le seuil de réussite (pass_mark) doit être entre 0 et 100, pas {}", mark)));
+    }
+    if quiz_file.time_limit == Some(0) {
+        issues.push(Issue::error(None, "la durée du quiz (time_limit) doit être positive".to_string()));
+    }
+
+    if quiz_file.metadata.id.as_deref().is_some_and(|id| id.trim().is_empty()) {
+        issues.push(Issue::error(None, "l'identifiant du quiz (metadata.id) est vide".to_string()));
+    }
+
+    let mut ids = HashSet::new();
+    for (i, quiz) in quiz_file.questions.iter().enumerate() {
+        let q = Some(i);
+
+        if let Some(id) = &quiz.id {
+            if id.trim().is_empty() {
+                issues.push(Issue::error(q, "l'identifiant de la question est vide".to_string()));
+            } else if !ids.insert(id.as_str()) {
+                issues.push(Issue::error(q, format!("l'identifiant « {} » est utilisé par plusieurs questions", id)));
+            }
+        }
+
+        if quiz.question.trim().is_empty() {
+            issues.push(Issue::error(q, "le texte de la question est vide".to_string()));
+        }
+
+        match (&quiz.image, &quiz.caption) {
+            (Some(image), _) if !Path::new(image).is_file() => {
+                issues.push(Issue::error(q, format!("image de la question introuvable : {}", image)));
+            }
+            (None, Some(_)) => {
+                issues.push(Issue::warning(q, "légende sans image de question".to_string()));
+            }
+            _ => {}
+        }
+
+        if quiz.points() <= 0.0 {
+            issues.push(Issue::error(q, "le nombre de points (points) doit être positif".to_string()));
+        }
+        if quiz.penalty.is_some_and(|penalty| penalty < 0.0) {
+            issues.push(Issue::error(q, "la pénalité (penalty) ne peut pas être négative".to_string()));
+        }
+        if quiz.time_limit == Some(0) {
+            issues.push(Issue::error(q, "le temps accordé (time_limit) doit être positif".to_string()));
+        }
+
+        match quiz.kind() {
+            QuestionKind::Single | QuestionKind::Multiple => validate_choices(quiz, q, &mut issues),
+            QuestionKind::Text => validate_text(quiz, q, &mut issues),
+            QuestionKind::Numeric => validate_numeric(quiz, q, &mut issues),
+            QuestionKind::Ordering => validate_ordering(quiz, q, &mut issues),
+            QuestionKind::Matching => validate_matching(quiz, q, &mut issues),
+            QuestionKind::Hotspot => validate_hotspot(quiz, q, &mut issues),
+        }
+    }
+
+    issues
+}
+
+fn validate_choices(quiz: &Quiz, q: Option<usize>, issues: &mut Vec<Issue>) {
+    if quiz.answers.is_empty() {
+        issues.push(Issue::error(q, "aucune réponse proposée".to_string()));
+    } else if quiz.answers.len() == 1 {
+        issues.push(Issue::warning(q, "une seule réponse proposée".to_string()));
+    }
+
+    let mut seen = HashSet::new();
+    for answer in &quiz.answers {
+        if !seen.insert(answer.letter.as_str()) {
+            issues.push(Issue::error(q, format!("la lettre « {} » est utilisée plusieurs fois", answer.letter)));
+        }
+        if answer.text.trim().is_empty() && answer.image.is_none() {
+            issues.push(Issue::warning(q, format!("la réponse {} n'a ni texte ni image", answer.letter)));
+        }
+        if let Some(image) = &answer.image
+            && !Path::new(image).is_file()
+        {
+            issues.push(Issue::error(q, format!("image introuvable pour la réponse {} : {}", answer.letter, image)));
+        }
+    }
+
+    let correct = quiz.correct_answer.letters();
+    if correct.is_empty() {
+        issues.push(Issue::error(q, "aucune bonne réponse indiquée".to_string()));
+    }
+    if quiz.kind() == QuestionKind::Single && correct.len() > 1 {
+        issues.push(Issue::error(
+            q,
+            "plusieurs bonnes réponses pour une question à réponse unique".to_string(),
+        ));
+    }
+    let mut seen_correct = HashSet::new();
+    for letter in correct {
+        if !seen_correct.insert(letter) {
+            issues.push(Issue::warning(q, format!("la bonne réponse « {} » est listée plusieurs fois", letter)));
+        }
+        if !quiz.answers.is_empty() && !seen.contains(letter) {
+            issues.push(Issue::error(
+                q,
+                format!("la bonne réponse « {} » ne correspond à aucune lettre", letter),
+            ));
+        }
+    }
+}
+
+fn validate_text(quiz: &Quiz, q: Option<usize>, issues: &mut Vec<Issue>) {
+    if quiz.accepted_answers.is_empty() {
+        issues.push(Issue::error(q, "aucune réponse acceptée pour une question à réponse libre".to_string()));
+    }
+    if !quiz.answers.is_empty() {
+        issues.push(Issue::warning(q, "les réponses proposées sont ignorées pour une réponse libre".to_string()));
+    }
+    if quiz.matching.regex {
+        for pattern in &quiz.accepted_answers {
+            if let Err(err) = compile_pattern(pattern, &quiz.matching) {
+                issues.push(Issue::error(q, format!("expression régulière invalide « {} » : {}", pattern, err)));
+            }
+        }
+    }
+}
+
+fn validate_numeric(quiz: &Quiz, q: Option<usize>, issues: &mut Vec<Issue>) {
+    let Some(expected) = &quiz.numeric else {
+        issues.push(Issue::error(q, "réponse numérique attendue manquante (`numeric`)".to_string()));
+        return;
+    };
+    if expected.value.is_none() && expected.min.is_none() && expected.max.is_none() {
+        issues.push(Issue::error(q, "ni valeur ni intervalle pour la réponse numérique".to_string()));
+    }
+    if let (Some(min), Some(max)) = (expected.min, expected.max)
+        && min > max
+    {
+        issues.push(Issue::error(q, format!("intervalle vide : min ({}) > max ({})", min, max)));
+    }
+    if expected.tolerance.is_some_and(|t| t < 0.0) || expected.relative_tolerance.is_some_and(|t| t < 0.0) {
+        issues.push(Issue::error(q, "tolérance négative".to_string()));
+    }
+    if !expected.units.is_empty() && expected.unit.is_none() {
+        issues.push(Issue::error(q, "des unités de conversion sont données sans unité de référence (`unit`)".to_string()));
+    }
+    for (unit, factor) in &expected.units {
+        if *factor <= 0.0 || !factor.is_finite() {
+            issues.push(Issue::error(q, format!("facteur de conversion invalide pour l'unité {} : {}", unit, factor)));
+        }
+    }
+}
+
+fn validate_ordering(quiz: &Quiz, q: Option<usize>, issues: &mut Vec<Issue>) {
+    if quiz.answers.len() < 2 {
+        issues.push(Issue::error(q, "il faut au moins deux éléments à remettre en ordre".to_string()));
+    }
+    let mut letters = HashSet::new();
+    for answer in &quiz.answers {
+        if !letters.insert(answer.letter.as_str()) {
+            issues.push(Issue::error(q, format!("la lettre « {} » est utilisée plusieurs fois", answer.letter)));
+        }
+    }
+
+    let expected = quiz.correct_answer.letters();
+    let expected_set: HashSet<&str> = expected.iter().copied().collect();
+    if expected.len() != expected_set.len() || expected_set != letters {
+        issues.push(Issue::error(
+            q,
+            "l'ordre attendu doit citer chaque lettre exactement une fois".to_string(),
+        ));
+    } else if quiz.answers.iter().map(|a| a.letter.as_str()).eq(expected.iter().copied()) {
+        issues.push(Issue::warning(q, "les éléments sont déjà listés dans le bon ordre".to_string()));
+    }
+}
+
+fn validate_matching(quiz: &Quiz, q: Option<usize>, issues: &mut Vec<Issue>) {
+    if quiz.answers.is_empty() || quiz.matches.is_empty() {
+        issues.push(Issue::error(q, "les deux colonnes (`answers` et `matches`) doivent être remplies".to_string()));
+    }
+
+    let mut left = HashSet::new();
+    let mut right = HashSet::new();
+    for (column, items, seen) in [("gauche", &quiz.answers, &mut left), ("droite", &quiz.matches, &mut right)] {
+        for item in items {
+            if !seen.insert(item.letter.as_str()) {
+                issues.push(Issue::error(
+                    q,
+                    format!("la lettre « {} » est utilisée plusieurs fois dans la colonne de {}", item.letter, column),
+                ));
+            }
+            if let Some(image) = &item.image
+                && !Path::new(image).is_file()
+            {
+                issues.push(Issue::error(q, format!("image introuvable pour l'élément {} : {}", item.letter, image)));
+            }
+        }
+    }
+
+    for (from, to) in &quiz.pairs {
+        if !left.contains(from.as_str()) {
+            issues.push(Issue::error(q, format!("association depuis une lettre inconnue : {}", from)));
+        }
+        if !right.contains(to.as_str()) {
+            issues.push(Issue::error(q, format!("association vers une lettre inconnue : {}", to)));
+        }
+    }
+    for answer in &quiz.answers {
+        if !quiz.pairs.contains_key(&answer.letter) {
+            issues.push(Issue::error(q, format!("aucune association attendue pour l'élément {}", answer.letter)));
+        }
+    }
+}
+
+fn validate_hotspot(quiz: &Quiz, q: Option<usize>, issues: &mut Vec<Issue>) {
+    let Some(hotspot) = &quiz.hotspot else {
+        issues.push(Issue::error(q, "image et zones manquantes (`hotspot`)".to_string()));
+        return;
+    };
+    if !Path::new(&hotspot.image).is_file() {
+        issues.push(Issue::error(q, format!("image introuvable : {}", hotspot.image)));
+    }
+    if hotspot.regions.is_empty() {
+        issues.push(Issue::error(q, "aucune zone juste définie sur l'image".to_string()));
+    }
+
+    let in_image = |v: f32| (0.0..=1.0).contains(&v);
+    for (r, region) in hotspot.regions.iter().enumerate() {
+        let valid = match region {
+            Region::Rect { x, y, width, height } => {
+                in_image(*x) && in_image(*y) && *width > 0.0 && *height > 0.0
+            }
+            Region::Circle { x, y, radius } => in_image(*x) && in_image(*y) && *radius > 0.0,
+            Region::Polygon { points } => points.len() >= 3 && points.iter().all(|[x, y]| in_image(*x) && in_image(*y)),
+        };
+        if !valid {
+            issues.push(Issue::error(
+                q,
+                format!("zone {} invalide (coordonnées relatives entre 0 et 1, taille positive, 3 points minimum)", r + 1),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(yaml: &str) -> Vec<Issue> {
+        validate_quiz_file(&serde_yaml::from_str(yaml).unwrap())
+    }
+
+    fn messages(issues: &[Issue], severity: Severity) -> Vec<&str> {
+        issues.iter().filter(|i| i.severity == severity).map(|i| i.message.as_str()).collect()
+    }
+
+    const VALID: &str = "questions:
+  - question: Capitale de la France ?
+    answers: [{letter: A, text: Paris}, {letter: B, text: Lyon}]
+    correct_answer: A
+  - question: Plus grand océan ?
+    answers: [{letter: A, text: Atlantique}, {letter: B, text: Pacifique}]
+    correct_answer: B";
+
+    #[test]
+    fn valid_quiz_has_no_issue() {
+        let issues = validate(VALID);
+        assert!(issues.is_empty(), "{:?}", issues);
+        assert!(!has_errors(&issues));
+    }
+
+    #[test]
+    fn empty_file_is_an_error() {
+        let issues = validate("questions: []");
+        assert!(has_errors(&issues));
+        assert_eq!(issues[0].question, None);
+    }
+
+    #[test]
+    fn choices_are_checked() {
+        let issues = validate(
+            "questions:
+  - question: Lequel ?
+    answers: [{letter: A, text: a}, {letter: A, text: b}]
+    correct_answer: C",
+        );
+        let errors = messages(&issues, Severity::Error);
+        assert!(errors.iter().any(|m| m.contains("utilisée plusieurs fois")));
+        assert!(errors.iter().any(|m| m.contains("ne correspond à aucune lettre")));
+    }
+
+    #[test]
+    fn single_answer_is_only_a_warning() {
+        let issues = validate(&VALID.replace(", {letter: B, text: Lyon}", ""));
+        assert!(!has_errors(&issues));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].question, Some(0));
+    }
+
+    #[test]
+    fn display_numbers_questions_from_one() {
+        let issue = Issue::error(Some(0), "problème".to_string());
+        assert_eq!(issue.to_string(), "Q1 : problème");
+        assert_eq!(Issue::warning(None, "global".to_string()).to_string(), "global");
+    }
+
+    #[test]
+    fn several_correct_answers_need_a_multiple_question() {
+        let single = validate(
+            "questions:
+  - question: Lequel ?
+    kind: single
+    answers: [{letter: A, text: a}, {letter: B, text: b}]
+    correct_answer: [A, B]",
+        );
+        assert!(messages(&single, Severity::Error).iter().any(|m| m.contains("réponse unique")));
+
+        let multiple = validate(
+            "questions:
+  - question: Lesquels ?
+    answers: [{letter: A, text: a}, {letter: B, text: b}]
+    correct_answer: [A, B]",
+        );
+        assert!(!has_errors(&multiple));
+    }
+
+    #[test]
+    fn numeric_range_must_not_be_empty() {
+        let issues = validate(
+            "questions:
+  - question: Combien ?
+    numeric: {min: 5, max: 2, tolerance: -1}",
+        );
+        let errors = messages(&issues, Severity::Error);
+        assert!(errors.iter().any(|m| m.starts_with("intervalle vide")));
+        assert!(errors.iter().any(|m| m.contains("tolérance négative")));
+    }
+
+    #[test]
+    fn ordering_must_cite_every_letter_once() {
+        let issues = validate(
+            "questions:
+  - question: Dans l'ordre ?
+    kind: ordering
+    answers: [{letter: A, text: a}, {letter: B, text: b}, {letter: C, text: c}]
+    correct_answer: [B, A, B]",
+        );
+        assert!(messages(&issues, Severity::Error).iter().any(|m| m.contains("exactement une fois")));
+
+        let sorted = validate(
+            "questions:
+  - question: Dans l'ordre ?
+    kind: ordering
+    answers: [{letter: A, text: a}, {letter: B, text: b}]
+    correct_answer: [A, B]",
+        );
+        assert!(!has_errors(&sorted));
+        assert_eq!(messages(&sorted, Severity::Warning).len(), 1);
+    }
+
+    #[test]
+    fn scoring_bounds_are_checked() {
+        let issues = validate(&format!("scoring: {{penalty: -1, pass_mark: 150}}\n{}", VALID));
+        let errors = messages(&issues, Severity::Error);
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|m| m.contains("penalty")));
+        assert!(errors.iter().any(|m| m.contains("pass_mark")));
+    }
+
+    #[test]
+    fn duplicate_question_ids_are_reported_on_the_second() {
+        let issues = validate(&VALID.replace("- question: ", "- id: q1\n    question: "));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(issues[0].question, Some(1));
+    }
+}
@@ -0,0 +1,42 @@
+use eframe::egui;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+
+/// Surveillance du dossier des quiz et de ses sous-dossiers, pour prendre en compte les fichiers
+/// modifiés pendant que l'application tourne.
+pub struct LibraryWatcher {
+    // La surveillance s'arrête quand le watcher est détruit
+    _watcher: RecommendedWatcher,
+    events: Receiver<Event>,
+}
+
+impl LibraryWatcher {
+    /// Chaque modification réveille l'interface, même si l'utilisateur ne la touche pas.
+    pub fn new(dir: &Path, ctx: egui::Context) -> notify::Result<Self> {
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| {
+            if let Ok(event) = result
+                && !matches!(event.kind, EventKind::Access(_))
+                && sender.send(event).is_ok()
+            {
+                ctx.request_repaint();
+            }
+        })?;
+        watcher.watch(&absolute_path(dir), RecursiveMode::Recursive)?;
+        Ok(Self { _watcher: watcher, events })
+    }
+
+    /// Chemins absolus créés, modifiés ou supprimés depuis l'appel précédent.
+    pub fn changed_paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self.events.try_iter().flat_map(|event| event.paths).collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+}
+
+/// Chemin absolu calculé sans accéder au disque, pour comparer aussi les fichiers supprimés.
+pub fn absolute_path(path: impl AsRef<Path>) -> PathBuf {
+    std::path::absolute(path.as_ref()).unwrap_or_else(|_| path.as_ref().to_path_buf())
+}